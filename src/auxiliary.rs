@@ -0,0 +1,87 @@
+use proc_macro_error::emit_call_site_error;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::generators::KeyGenerator;
+
+/// The types generated for keys, such as enums and flags, which are only
+/// emitted once for the structs generated in one invocation
+pub struct AuxiliaryTypes<'k, 'e> {
+    /// The definitions of the types emitted for the previous structs, by name
+    emitted: &'e HashMap<String, String>,
+    struct_name: String,
+    /// Names of the other types generated for the struct, such as its builder
+    struct_type_names: HashSet<String>,
+    /// Names of the keys that generate each type, by the name of the type
+    key_names: HashMap<String, &'k str>,
+    pub token_stream: proc_macro2::TokenStream,
+    /// The definitions of the types emitted for this struct, by name
+    pub definitions: HashMap<String, String>,
+}
+
+impl<'k, 'e> AuxiliaryTypes<'k, 'e> {
+    pub fn new(
+        emitted: &'e HashMap<String, String>,
+        struct_name: String,
+        struct_type_names: HashSet<String>,
+    ) -> Self {
+        Self {
+            emitted,
+            struct_name,
+            struct_type_names,
+            key_names: HashMap::new(),
+            token_stream: proc_macro2::TokenStream::new(),
+            definitions: HashMap::new(),
+        }
+    }
+
+    /// Adds the type generated for the key named `key_name`, if any.
+    ///
+    /// Generated types are named after their keys, so they can clash with each
+    /// other, with the struct itself, or with the other types generated for it.
+    pub fn push(&mut self, key_name: &'k str, generator: &KeyGenerator<'_>) {
+        if let (Some(aux), Some(type_name)) =
+            (generator.auxiliary(), generator.auxiliary_type_name())
+        {
+            let aux_definition = aux.to_string();
+            match self.emitted.get(type_name) {
+                Some(other_definition) if *other_definition == aux_definition => (),
+                Some(_) => emit_call_site_error!(
+                    "type `{}` generated for key `{}` differs from the one generated for another struct; consider using `#[gen_settings_define( .. )]`",
+                    type_name,
+                    key_name,
+                ),
+                None => {
+                    self.definitions
+                        .insert(type_name.to_string(), aux_definition);
+                    self.token_stream.extend(aux);
+                }
+            }
+        }
+
+        if let Some(type_name) = generator.auxiliary_type_name() {
+            if type_name == self.struct_name {
+                emit_call_site_error!(
+                    "type `{}` generated for key `{}` has the same name as the struct; consider renaming the struct or using `#[gen_settings_define( .. )]`",
+                    type_name,
+                    key_name,
+                );
+            } else if self.struct_type_names.contains(type_name) {
+                emit_call_site_error!(
+                    "type `{}` generated for key `{}` has the same name as a type generated for the struct; consider renaming the struct or using `#[gen_settings_define( .. )]`",
+                    type_name,
+                    key_name,
+                );
+            } else if let Some(other_key_name) =
+                self.key_names.insert(type_name.to_string(), key_name)
+            {
+                emit_call_site_error!(
+                    "keys `{}` and `{}` both generate a type named `{}`; consider using `#[gen_settings_define( .. )]` or skipping one of them with `#[gen_settings_skip( .. )]`",
+                    other_key_name,
+                    key_name,
+                    type_name,
+                );
+            }
+        }
+    }
+}
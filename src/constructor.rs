@@ -0,0 +1,269 @@
+use quote::{format_ident, quote};
+
+use crate::generators::{Crates, StructInfo};
+
+/// The constructors of the struct and the items that go along with them
+pub struct Constructors {
+    /// The constructors in the impl block of the struct
+    pub methods: proc_macro2::TokenStream,
+    /// The builder of the struct and the implementation of `Default`
+    pub items: proc_macro2::TokenStream,
+    /// Names of the constructors, including `default`
+    pub method_names: Vec<&'static str>,
+}
+
+/// Generates `new`, `try_new`, and `builder`, along with the constructors that
+/// only apply to some schemas.
+///
+/// The constructors take the schema id as a parameter if `schema_id` is not
+/// specified. `schema_path` is the path specified in the attributes, so a
+/// schema is only constructed without a path if it is relocatable and
+/// `schema_path` is not specified.
+pub fn constructors(
+    info: &StructInfo<'_>,
+    constructor_vis: &syn::Visibility,
+    schema_id: Option<&str>,
+    schema_path: Option<&str>,
+    schema_path_template: Option<&str>,
+    is_relocatable: bool,
+) -> Constructors {
+    let StructInfo {
+        ident: struct_ident,
+        vis: struct_vis,
+        field,
+        crates: Crates { gio, glib },
+    } = info;
+    let mut method_names = vec!["new", "try_new", "builder"];
+
+    let (constructor_params, schema_id_expr) = if let Some(schema_id) = schema_id {
+        (quote! {}, quote! { #schema_id })
+    } else {
+        (quote! { schema_id: &str }, quote! { schema_id })
+    };
+    let construct_new = field.construct(if let Some(schema_path) = schema_path {
+        quote! { #gio::Settings::with_path(#schema_id_expr, #schema_path) }
+    } else {
+        quote! { #gio::Settings::new(#schema_id_expr) }
+    });
+    let relocatable_check = if schema_path.is_none() {
+        quote! {
+            if #gio::SettingsSchema::path(&schema).is_none() {
+                return Err(#glib::bool_error!(
+                    "schema `{}` is relocatable and requires a path",
+                    #schema_id_expr
+                ));
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let mut methods = quote! {
+        #constructor_vis fn new(#constructor_params) -> Self {
+            #construct_new
+        }
+
+        /// Creates the settings, like `new`, but returns an error instead of
+        /// aborting if the schema is not installed.
+        #constructor_vis fn try_new(#constructor_params) -> std::result::Result<Self, #glib::BoolError> {
+            let schema = #gio::SettingsSchemaSource::default()
+                .and_then(|source| source.lookup(#schema_id_expr, true))
+                .ok_or_else(|| #glib::bool_error!("schema `{}` is not installed", #schema_id_expr))?;
+            #relocatable_check
+
+            Ok(#construct_new)
+        }
+    };
+    if is_relocatable {
+        method_names.push("with_path_and_backend");
+        let construct_with_path_and_backend = field.construct(quote! {
+            #gio::Settings::with_backend_and_path(#schema_id_expr, backend, path)
+        });
+        let with_path_and_backend_params = if schema_id.is_some() {
+            quote! { path: &str, backend: &impl #glib::object::IsA<#gio::SettingsBackend> }
+        } else {
+            quote! { schema_id: &str, path: &str, backend: &impl #glib::object::IsA<#gio::SettingsBackend> }
+        };
+        methods.extend(quote! {
+            /// Creates the settings with the given path and backend.
+            #constructor_vis fn with_path_and_backend(#with_path_and_backend_params) -> Self {
+                #construct_with_path_and_backend
+            }
+        });
+    }
+    if let Some(schema_path_template) = schema_path_template {
+        method_names.push("for_profile");
+        let for_profile_params = if schema_id.is_some() {
+            quote! { profile: &str }
+        } else {
+            quote! { schema_id: &str, profile: &str }
+        };
+        let docs = format!(
+            "Creates settings with the path `{}`, where `{{}}` is replaced by `profile`.\n\n\
+            # Panics\n\n\
+            Panics if `profile` is empty or contains a slash.",
+            schema_path_template
+        );
+        let construct_for_profile =
+            field.construct(quote! { #gio::Settings::with_path(#schema_id_expr, &path) });
+        methods.extend(quote! {
+            #[doc = #docs]
+            #constructor_vis fn for_profile(#for_profile_params) -> Self {
+                assert!(
+                    !profile.is_empty() && !profile.contains('/'),
+                    "invalid profile `{}`: must not be empty nor contain a slash",
+                    profile
+                );
+
+                let path = #schema_path_template.replacen("{}", profile, 1);
+                #construct_for_profile
+            }
+        });
+    }
+
+    let builder_ident = format_ident!("{}Builder", struct_ident);
+    let builder_docs = format!("A builder for [`{}`].", struct_ident);
+    let builder_path_expr = if let Some(schema_path) = schema_path {
+        quote! { Some(#schema_path.to_string()) }
+    } else {
+        quote! { None }
+    };
+    let construct_built = field.construct_as(quote! { #struct_ident }, quote! { settings });
+    methods.extend(quote! {
+        /// Creates a builder to configure the backend, path, and delay-apply
+        /// mode of the settings.
+        #constructor_vis fn builder(#constructor_params) -> #builder_ident {
+            #builder_ident {
+                schema_id: #schema_id_expr.to_string(),
+                path: #builder_path_expr,
+                backend: None,
+                delayed: false,
+            }
+        }
+    });
+    // Relocatable schemas would need a path, which can't be passed with `builder`
+    let has_fixed_path = !is_relocatable || schema_path.is_some();
+    let (with_defaults_params, builder_args) = if schema_id.is_some() {
+        (quote! { defaults: &[(&str, #glib::Variant)] }, quote! {})
+    } else {
+        (
+            quote! { schema_id: &str, defaults: &[(&str, #glib::Variant)] },
+            quote! { schema_id },
+        )
+    };
+    if has_fixed_path {
+        method_names.push("new_with_defaults");
+        methods.extend(quote! {
+            /// Creates the settings with a new in-memory backend, where the keys
+            /// in `defaults` are set to the given values, for example, to start
+            /// tests from specific values without editing the schema.
+            ///
+            /// Resetting a key still restores the default value in the schema.
+            ///
+            /// # Panics
+            ///
+            /// Panics if a key is not in the schema or the type of its value does
+            /// not match the type of the key.
+            #constructor_vis fn new_with_defaults(#with_defaults_params) -> Self {
+                let this = Self::builder(#builder_args)
+                    .backend(&#gio::memory_settings_backend_new())
+                    .build();
+
+                let schema = #gio::prelude::SettingsExt::settings_schema(&*this)
+                    .expect("settings must have a schema");
+                for (key_name, value) in defaults {
+                    assert!(
+                        schema.has_key(key_name),
+                        "key `{}` is not in the schema",
+                        key_name
+                    );
+                    let value_type = schema.key(key_name).value_type();
+                    assert!(
+                        value.type_() == value_type,
+                        "expected a value of type `{}` for key `{}`, got `{}`",
+                        value_type,
+                        key_name,
+                        value.type_()
+                    );
+                    #gio::prelude::SettingsExt::set_value(&*this, key_name, value)
+                        .unwrap_or_else(|err| panic!("failed to set value for key `{}`: {:?}", key_name, err));
+                }
+
+                this
+            }
+        });
+    }
+    let mut items = quote! {
+        #[doc = #builder_docs]
+        #[derive(Debug, Clone)]
+        #[must_use = "the builder does nothing unless `build` is called"]
+        #struct_vis struct #builder_ident {
+            schema_id: String,
+            path: Option<String>,
+            backend: Option<#gio::SettingsBackend>,
+            delayed: bool,
+        }
+
+        impl #builder_ident {
+            /// Sets the path of the settings, which is required for relocatable schemas.
+            pub fn path(mut self, path: &str) -> Self {
+                self.path = Some(path.to_string());
+                self
+            }
+
+            /// Sets the backend of the settings, instead of using the default one.
+            pub fn backend(mut self, backend: &impl #glib::object::IsA<#gio::SettingsBackend>) -> Self {
+                self.backend = Some(#glib::object::Cast::upcast_ref::<#gio::SettingsBackend>(backend).clone());
+                self
+            }
+
+            /// Sets whether the settings are in delay-apply mode.
+            pub fn delayed(mut self, delayed: bool) -> Self {
+                self.delayed = delayed;
+                self
+            }
+
+            /// Builds the settings.
+            pub fn build(self) -> #struct_ident {
+                let settings = match (self.backend, self.path) {
+                    (Some(backend), Some(path)) => {
+                        #gio::Settings::with_backend_and_path(&self.schema_id, &backend, &path)
+                    }
+                    (Some(backend), None) => #gio::Settings::with_backend(&self.schema_id, &backend),
+                    (None, Some(path)) => #gio::Settings::with_path(&self.schema_id, &path),
+                    (None, None) => #gio::Settings::new(&self.schema_id),
+                };
+
+                if self.delayed {
+                    #gio::prelude::SettingsExt::delay(&settings);
+                }
+
+                #construct_built
+            }
+        }
+    };
+
+    // `Default` can't be restricted, so it is only implemented if the
+    // constructors are public
+    let is_constructor_public = matches!(constructor_vis, syn::Visibility::Public(_));
+    if schema_id.is_some() && is_constructor_public {
+        method_names.push("default");
+        let construct_default = if field.is_lazy() {
+            field.construct_uninit()
+        } else {
+            quote! { Self::new() }
+        };
+        items.extend(quote! {
+            impl Default for #struct_ident {
+                fn default() -> Self {
+                    #construct_default
+                }
+            }
+        });
+    }
+
+    Constructors {
+        methods,
+        items,
+        method_names,
+    }
+}
@@ -0,0 +1,99 @@
+use quote::{format_ident, quote};
+
+use crate::{
+    generators::{Crates, StructInfo},
+    schema::Key as SchemaKey,
+};
+
+/// Generates the key descriptor struct and `describe`, which returns the
+/// descriptors of `keys`
+pub fn describe_token_stream(
+    info: &StructInfo<'_>,
+    keys: &[SchemaKey],
+) -> proc_macro2::TokenStream {
+    let StructInfo {
+        ident: struct_ident,
+        vis: struct_vis,
+        crates: Crates { glib, .. },
+        ..
+    } = info;
+
+    let descriptor_ident = format_ident!("{}KeyDescriptor", struct_ident);
+    let descriptor_docs = format!(
+        "The metadata of a key of [`{}`], as specified in the schema.",
+        struct_ident
+    );
+    let non_empty = |text: &Option<String>| match text
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+    {
+        Some(text) => quote! { Some(#text) },
+        None => quote! { None },
+    };
+    let descriptors = keys.iter().map(|key| {
+        let key_name = key.name.as_str();
+        let signature = key
+            .signature()
+            .expect("signatures are validated before generating")
+            .storage_type()
+            .to_string();
+        let summary = non_empty(&key.summary);
+        let description = non_empty(&key.description);
+        let default_value = key.default_value();
+        let range =
+            match key.range.as_ref().and_then(|range| {
+                Some((range.min.as_deref()?.trim(), range.max.as_deref()?.trim()))
+            }) {
+                Some((min, max)) => quote! { Some((parse(#min), parse(#max))) },
+                None => quote! { None },
+            };
+
+        quote! {
+            {
+                let type_ = #glib::VariantTy::new(#signature).unwrap();
+                let parse = |text: &str| {
+                    #glib::Variant::parse(Some(type_), text).unwrap_or_else(|err| {
+                        panic!("invalid value `{}` for key `{}`: {}", text, #key_name, err)
+                    })
+                };
+
+                #descriptor_ident {
+                    name: #key_name,
+                    signature: #signature,
+                    summary: #summary,
+                    description: #description,
+                    default_value: parse(#default_value),
+                    range: #range,
+                }
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #descriptor_docs]
+        #[derive(Clone, Debug, PartialEq)]
+        #struct_vis struct #descriptor_ident {
+            /// The name of the key.
+            pub name: &'static str,
+            /// The type signature of the values of the key, where enums are
+            /// `s` and flags are `as`.
+            pub signature: &'static str,
+            /// The summary of the key, if it is not empty.
+            pub summary: Option<&'static str>,
+            /// The description of the key, if it is not empty.
+            pub description: Option<&'static str>,
+            /// The default value of the key, as specified in the schema.
+            pub default_value: #glib::Variant,
+            /// The minimum and maximum values of the key, if it has a range.
+            pub range: Option<(#glib::Variant, #glib::Variant)>,
+        }
+
+        impl #struct_ident {
+            /// Describes all the keys in the schema, in the order they are defined.
+            pub fn describe() -> Vec<#descriptor_ident> {
+                vec![#(#descriptors),*]
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use quote::quote;
+use syn::Ident;
+
+use std::collections::HashSet;
+
+use crate::generators::{check_method_names, KeyGenerator, StructInfo};
+
+/// The module with free functions taking a `gio::Settings` for each key
+pub struct FunctionsModule<'k> {
+    ident: Ident,
+    functions: proc_macro2::TokenStream,
+    function_names: Vec<(&'k str, Vec<String>)>,
+}
+
+impl<'k> FunctionsModule<'k> {
+    pub fn new(ident: Ident) -> Self {
+        Self {
+            ident,
+            functions: proc_macro2::TokenStream::new(),
+            function_names: Vec::new(),
+        }
+    }
+
+    /// Adds the functions of the key named `key_name`
+    pub fn push(&mut self, key_name: &'k str, generator: &KeyGenerator<'_>, struct_ident: &Ident) {
+        let items = generator.free_function_items(struct_ident);
+        self.functions.extend(items.tokens);
+        self.function_names.push((key_name, items.names));
+    }
+
+    pub fn token_stream(&self, info: &StructInfo<'_>) -> proc_macro2::TokenStream {
+        let StructInfo {
+            ident: struct_ident,
+            vis: struct_vis,
+            ..
+        } = info;
+        let Self {
+            ident, functions, ..
+        } = self;
+
+        let module_docs = format!(
+            "Free functions taking a `gio::Settings` for each key of [`{}`].",
+            struct_ident
+        );
+        quote! {
+            #[doc = #module_docs]
+            #struct_vis mod #ident {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #functions
+            }
+        }
+    }
+
+    /// Emits an error for each function that is generated for multiple keys
+    pub fn check_method_names(self) {
+        check_method_names(
+            &format!("`{}`", self.ident),
+            self.function_names,
+            &HashSet::new(),
+        );
+    }
+}
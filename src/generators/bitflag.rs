@@ -1,34 +1,164 @@
-use heck::{ToPascalCase, ToShoutySnakeCase};
-use quote::quote;
-use syn::{spanned::Spanned, Ident};
+use heck::ToShoutySnakeCase;
+use quote::{format_ident, quote};
+use syn::Ident;
 
-use super::{Context, KeyGenerator, SchemaFlag, SchemaKey};
+use super::{Context, Crates, Field, Items, KeyGenerator, SchemaFlag, SchemaKey};
 
 pub fn key_generator<'a>(
     key: &'a SchemaKey,
+    flag_name: &str,
     flag: &SchemaFlag,
     aux_visibility: syn::Visibility,
+    crates: &'a Crates,
+    field: &'a Field,
 ) -> KeyGenerator<'a> {
-    let flag_name = key.name.to_pascal_case();
+    let ident = super::generated_ident(flag_name, format_args!("key `{}`", key.name));
     KeyGenerator::new(
         key,
         Context::new_with_aux(
-            &flag_name,
-            bitflag_token_stream(&flag_name, flag, aux_visibility),
+            flag_name,
+            bitflag_token_stream(&ident, flag, aux_visibility, crates),
         ),
+        crates,
+        field,
+    )
+    .with_extra_methods(flag_actions_items(key, &ident, flag, crates, field))
+}
+
+/// The identifiers of the flags of the type named `ident`
+fn value_idents<'a>(ident: &'a Ident, flag: &'a SchemaFlag) -> impl Iterator<Item = Ident> + 'a {
+    flag.values.iter().map(move |value| {
+        super::generated_ident(
+            &value.nick.to_shouty_snake_case(),
+            format_args!("nick `{}` of `{}`", value.nick, ident),
+        )
+    })
+}
+
+/// Creates a method that returns an action group containing a stateful boolean
+/// action, named after the nick, for each flag of the key
+fn flag_actions_items(
+    key: &SchemaKey,
+    ident: &Ident,
+    flag: &SchemaFlag,
+    crates: &Crates,
+    field: &Field,
+) -> Items {
+    let Crates { gio, glib } = crates;
+    let settings = field.access();
+
+    let key_name = key.name.as_str();
+    let getter_func_ident = super::key_func_ident(key_name);
+    let try_setter_func_ident = format_ident!("try_set_{}", getter_func_ident);
+    let connect_changed_func_ident = format_ident!("connect_{}_changed", getter_func_ident);
+    let create_flag_actions_func_ident = format_ident!("create_{}_flag_actions", getter_func_ident);
+
+    let value_idents = value_idents(ident, flag);
+    let value_nicks = flag.values.iter().map(|value| value.nick.as_str());
+
+    let docs = format!(
+        "Creates a `SimpleActionGroup` with a stateful boolean action for each flag of `{}`.\n\n\
+        Each action is named after the flag's nick and toggles its flag when activated. \
+        The state of the actions is kept in sync with the key, and they are only enabled \
+        while the key is writable, until the group is finalized. A state that can't be \
+        written is logged and not applied.",
+        key_name
+    );
+
+    Items::new(
+        [&create_flag_actions_func_ident],
+        quote! {
+            #[doc = #docs]
+            pub fn #create_flag_actions_func_ident(&self) -> #gio::SimpleActionGroup {
+                let group = #gio::SimpleActionGroup::new();
+                let value = self.#getter_func_ident();
+                let is_writable = #gio::prelude::SettingsExt::is_writable(&#settings, #key_name);
+
+                let actions = [#(
+                    (
+                        #ident::#value_idents,
+                        #gio::SimpleAction::new_stateful(
+                            #value_nicks,
+                            None,
+                            &#glib::ToVariant::to_variant(&value.contains(#ident::#value_idents)),
+                        ),
+                    )
+                ),*];
+
+                for (flag, action) in &actions {
+                    let flag = *flag;
+                    let this = self.clone();
+                    action.set_enabled(is_writable);
+                    action.connect_change_state(move |action, state| {
+                        if let Some(state) = state.and_then(#glib::Variant::get::<bool>) {
+                            let mut value = this.#getter_func_ident();
+                            value.set(flag, state);
+                            if let Err(err) = this.#try_setter_func_ident(value) {
+                                #glib::g_warning!(
+                                    "gsettings-macro",
+                                    "failed to set key `{}` from the state of action `{}`: {}",
+                                    #key_name,
+                                    #gio::prelude::ActionExt::name(action),
+                                    err
+                                );
+                            }
+                        }
+                    });
+                    #gio::prelude::ActionMapExt::add_action(&group, action);
+                }
+
+                let weak_actions = actions.map(|(flag, action)| {
+                    (flag, #glib::prelude::ObjectExt::downgrade(&action))
+                });
+                let writable_weak_actions = weak_actions.clone();
+                let changed_handler_id = self.#connect_changed_func_ident(move |_, value| {
+                    for (flag, action) in &weak_actions {
+                        if let Some(action) = action.upgrade() {
+                            action.set_state(&#glib::ToVariant::to_variant(&value.contains(*flag)));
+                        }
+                    }
+                });
+                let writable_changed_handler_id = #gio::prelude::SettingsExt::connect_writable_changed(&#settings, Some(#key_name), move |settings, _| {
+                    let is_writable = #gio::prelude::SettingsExt::is_writable(settings, #key_name);
+                    for (_, action) in &writable_weak_actions {
+                        if let Some(action) = action.upgrade() {
+                            action.set_enabled(is_writable);
+                        }
+                    }
+                });
+
+                // The handlers only update the actions, so they are disconnected
+                // once the group, which holds the actions, is finalized
+                let settings_weak = #glib::prelude::ObjectExt::downgrade(&#settings);
+                #glib::prelude::ObjectExt::add_weak_ref_notify_local(&group, move || {
+                    if let Some(settings) = settings_weak.upgrade() {
+                        #glib::prelude::ObjectExt::disconnect(&settings, changed_handler_id);
+                        #glib::prelude::ObjectExt::disconnect(&settings, writable_changed_handler_id);
+                    }
+                });
+
+                group
+            }
+        },
     )
 }
 
 fn bitflag_token_stream(
-    name: &str,
+    ident: &Ident,
     flag: &SchemaFlag,
     visibility: syn::Visibility,
+    crates: &Crates,
 ) -> proc_macro2::TokenStream {
-    let value_idents = flag
-        .values
-        .iter()
-        .map(|value| Ident::new(&value.nick.to_shouty_snake_case(), value.nick.span()))
-        .collect::<Vec<_>>();
+    let glib = &crates.glib;
+
+    super::ensure_unique_idents(
+        &ident.to_string(),
+        flag.values
+            .iter()
+            .map(|value| (value.nick.as_str(), value.nick.to_shouty_snake_case())),
+    );
+
+    let value_idents = value_idents(ident, flag).collect::<Vec<_>>();
 
     let flags_arms = value_idents
         .iter()
@@ -64,25 +194,22 @@ fn bitflag_token_stream(
                 }
             });
 
-    let name_pascal_case = name.to_pascal_case();
-    let ident = Ident::new(&name_pascal_case, name_pascal_case.span());
-
     quote! {
-        gio::glib::bitflags::bitflags! {
+        #glib::bitflags::bitflags! {
             #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
             #visibility struct #ident: u32 {
                 #(#flags_arms)*
             }
         }
 
-        impl gio::glib::StaticVariantType for #ident {
-            fn static_variant_type() -> std::borrow::Cow<'static, gio::glib::VariantTy> {
-                std::borrow::Cow::Borrowed(gio::glib::VariantTy::STRING_ARRAY)
+        impl #glib::StaticVariantType for #ident {
+            fn static_variant_type() -> std::borrow::Cow<'static, #glib::VariantTy> {
+                std::borrow::Cow::Borrowed(#glib::VariantTy::STRING_ARRAY)
             }
         }
 
-        impl gio::glib::FromVariant for #ident {
-            fn from_variant(variant: &gio::glib::Variant) -> Option<Self> {
+        impl #glib::FromVariant for #ident {
+            fn from_variant(variant: &#glib::Variant) -> Option<Self> {
                 let mut this = Self::empty();
 
                 for string in variant.get::<Vec<String>>()? {
@@ -96,19 +223,19 @@ fn bitflag_token_stream(
             }
         }
 
-        impl gio::glib::ToVariant for #ident {
-            fn to_variant(&self) -> gio::glib::Variant {
+        impl #glib::ToVariant for #ident {
+            fn to_variant(&self) -> #glib::Variant {
                 let mut string_array = Vec::new();
 
                 #(#to_variant_arms)*
 
-                gio::glib::ToVariant::to_variant(&string_array)
+                #glib::ToVariant::to_variant(&string_array)
             }
         }
 
-        impl std::convert::From<#ident> for gio::glib::Variant {
-            fn from(this: #ident) -> gio::glib::Variant {
-                gio::glib::ToVariant::to_variant(&this)
+        impl std::convert::From<#ident> for #glib::Variant {
+            fn from(this: #ident) -> #glib::Variant {
+                #glib::ToVariant::to_variant(&this)
             }
         }
     }
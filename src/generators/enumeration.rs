@@ -1,21 +1,38 @@
-use heck::ToPascalCase;
-
-use super::{Context, KeyGenerator, SchemaEnum, SchemaKey};
+use super::{Context, Crates, Field, KeyGenerator, SchemaEnum, SchemaKey};
 
 pub fn key_generator<'a>(
     key: &'a SchemaKey,
+    enum_name: &str,
     enum_: &SchemaEnum,
     aux_visibility: syn::Visibility,
+    crates: &'a Crates,
+    field: &'a Field,
 ) -> KeyGenerator<'a> {
-    let enum_name = key.name.to_pascal_case();
     let enum_token_stream = super::new_variant_enum(
-        &enum_name,
+        enum_name,
         &enum_
             .values
             .iter()
             .map(|value| (value.nick.as_str(), Some(value.value)))
             .collect::<Vec<_>>(),
+        key,
         aux_visibility,
+        crates,
     );
-    KeyGenerator::new(key, Context::new_with_aux(&enum_name, enum_token_stream))
+    let nicks = enum_
+        .values
+        .iter()
+        .map(|value| value.nick.as_str())
+        .collect::<Vec<_>>();
+    KeyGenerator::new(
+        key,
+        Context::new_with_aux(enum_name, enum_token_stream),
+        crates,
+        field,
+    )
+    .with_extra_methods({
+        let mut extra_methods = super::nick_getter_items(key, &nicks, crates, field);
+        extra_methods.append(super::from_str_setter_items(key, enum_name, crates));
+        extra_methods
+    })
 }
@@ -1,25 +1,340 @@
 mod bitflag;
 mod enumeration;
+mod signature;
 mod string;
 
-use heck::ToSnakeCase;
-use proc_macro2::Span;
-use proc_macro_error::abort_call_site;
-use quote::{format_ident, quote};
+use heck::{ToPascalCase, ToSnakeCase};
+use proc_macro_error::{abort_call_site, emit_call_site_error};
+use quote::{format_ident, quote, ToTokens};
 use std::collections::{HashMap, HashSet};
 use syn::Ident;
 
-use std::fmt::Write;
+use std::{fmt::Write, str::FromStr};
 
 use crate::schema::{
     Enum as SchemaEnum, Flag as SchemaFlag, Key as SchemaKey, KeySignature as SchemaKeySignature,
 };
 
+pub use signature::value_types;
+
+/// Paths to the crates used by the generated code
+pub struct Crates {
+    pub gio: syn::Path,
+    pub glib: syn::Path,
+}
+
+/// The generated struct, which the items generated along with it refer to
+pub struct StructInfo<'a> {
+    pub ident: &'a Ident,
+    pub vis: &'a syn::Visibility,
+    pub field: &'a Field,
+    pub crates: &'a Crates,
+}
+
+/// The field of the generated struct that holds the [`gio::Settings`]
+pub struct Field {
+    member: syn::Member,
+    /// The expression creating the settings on first access, if the field is a
+    /// `OnceCell` that is initialized lazily
+    lazy_init: Option<proc_macro2::TokenStream>,
+}
+
+impl Field {
+    /// A named field if `name` is given, otherwise the field of a tuple struct
+    pub fn new(name: Option<Ident>) -> Self {
+        Self {
+            member: name.map_or_else(|| syn::Member::Unnamed(0.into()), syn::Member::Named),
+            lazy_init: None,
+        }
+    }
+
+    /// Make the field a `OnceCell` that is initialized with `init` on first access
+    pub fn with_lazy_init(mut self, init: proc_macro2::TokenStream) -> Self {
+        self.lazy_init = Some(init);
+        self
+    }
+
+    pub fn is_named(&self) -> bool {
+        matches!(self.member, syn::Member::Named(_))
+    }
+
+    pub fn is_lazy(&self) -> bool {
+        self.lazy_init.is_some()
+    }
+
+    /// An expression that accesses the field of `self`
+    pub fn access(&self) -> proc_macro2::TokenStream {
+        self.access_of(quote! { self })
+    }
+
+    /// An expression that accesses the field of the given value
+    pub fn access_of(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let member = &self.member;
+        match self.lazy_init {
+            Some(ref init) => quote! { (*#value.#member.get_or_init(|| #init)) },
+            None => quote! { #value.#member },
+        }
+    }
+
+    /// An expression that mutably borrows the settings of `self`
+    pub fn access_mut(&self) -> proc_macro2::TokenStream {
+        let member = &self.member;
+        match self.lazy_init {
+            Some(ref init) => quote! {
+                {
+                    self.#member.get_or_init(|| #init);
+                    self.#member.get_mut().unwrap()
+                }
+            },
+            None => quote! { &mut self.#member },
+        }
+    }
+
+    /// An expression that constructs `Self` with the given settings
+    pub fn construct(&self, settings: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        self.construct_as(quote! { Self }, settings)
+    }
+
+    /// An expression that constructs the given struct with the given settings
+    pub fn construct_as(
+        &self,
+        struct_path: proc_macro2::TokenStream,
+        settings: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let settings = if self.is_lazy() {
+            quote! { std::cell::OnceCell::from(#settings) }
+        } else {
+            settings
+        };
+
+        match self.member {
+            syn::Member::Named(ref ident) => quote! { #struct_path { #ident: #settings } },
+            syn::Member::Unnamed(_) => quote! { #struct_path(#settings) },
+        }
+    }
+
+    /// An expression that constructs `Self` without creating the settings, which
+    /// is only possible if the field is initialized lazily
+    pub fn construct_uninit(&self) -> proc_macro2::TokenStream {
+        debug_assert!(self.is_lazy());
+
+        match self.member {
+            syn::Member::Named(ref ident) => {
+                quote! { Self { #ident: std::cell::OnceCell::new() } }
+            }
+            syn::Member::Unnamed(_) => quote! { Self(std::cell::OnceCell::new()) },
+        }
+    }
+}
+
 pub enum OverrideType {
-    Define { arg_type: String, ret_type: String },
+    Define {
+        arg_type: String,
+        ret_type: String,
+        force: bool,
+    },
     Skip,
 }
 
+/// A category of container type signatures that can be skipped at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContainerKind {
+    /// Arrays other than dictionaries, such as `as` and `a(ss)`
+    Array,
+    /// Dictionaries, such as `a{sv}`
+    Dict,
+    /// Tuples, such as `(ii)`
+    Tuple,
+}
+
+impl ContainerKind {
+    /// The kind of the key with the given signature, if it is a container.
+    /// Flags are not containers, even though they are stored as `as`.
+    pub fn of(signature: &SchemaKeySignature) -> Option<Self> {
+        let SchemaKeySignature::Type(type_) = signature else {
+            return None;
+        };
+
+        if type_.starts_with("a{") {
+            Some(Self::Dict)
+        } else if type_.starts_with('a') {
+            Some(Self::Array)
+        } else if type_.starts_with('(') {
+            Some(Self::Tuple)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for ContainerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "array" => Ok(Self::Array),
+            "dict" => Ok(Self::Dict),
+            "tuple" => Ok(Self::Tuple),
+            _ => Err(format!(
+                "unknown kind `{}`; expected one of `array`, `dict`, or `tuple`",
+                s
+            )),
+        }
+    }
+}
+
+/// Names of the methods of the struct that are generated regardless of the
+/// options, along with the trait methods that a key method would shadow when
+/// called with method syntax
+pub const RESERVED_METHOD_NAMES: &[&str] = &[
+    "from_settings",
+    "reset_all",
+    "schema_path",
+    "children",
+    "iter_keys",
+    "dump",
+    "connect_any_changed",
+    "connect_applied",
+    "clone",
+    "deref",
+    "deref_mut",
+    "fmt",
+    "eq",
+    "hash",
+];
+
+/// Names of the methods of `SettingsExt` and `SettingsExtManual`, which calls
+/// to the methods of the extension trait on `gio::Settings` would be ambiguous
+/// with
+pub const SETTINGS_EXT_METHOD_NAMES: &[&str] = &[
+    "apply",
+    "backend",
+    "bind",
+    "bind_writable",
+    "boolean",
+    "child",
+    "connect_change_event",
+    "connect_changed",
+    "connect_delay_apply_notify",
+    "connect_has_unapplied_notify",
+    "connect_writable_change_event",
+    "connect_writable_changed",
+    "create_action",
+    "default_value",
+    "delay",
+    "double",
+    "enum_",
+    "flags",
+    "get",
+    "has_unapplied",
+    "int",
+    "int64",
+    "is_delay_apply",
+    "is_writable",
+    "list_children",
+    "mapped",
+    "path",
+    "reset",
+    "revert",
+    "schema_id",
+    "set",
+    "set_boolean",
+    "set_double",
+    "set_enum",
+    "set_flags",
+    "set_int",
+    "set_int64",
+    "set_string",
+    "set_strv",
+    "set_uint",
+    "set_uint64",
+    "set_value",
+    "settings_schema",
+    "string",
+    "strv",
+    "uint",
+    "uint64",
+    "user_value",
+    "value",
+];
+
+/// Generated items, along with the names they define, so collisions can be
+/// checked without inspecting the tokens.
+#[derive(Clone, Default)]
+pub struct Items {
+    pub tokens: proc_macro2::TokenStream,
+    pub names: Vec<String>,
+}
+
+impl Items {
+    fn new<'i>(
+        idents: impl IntoIterator<Item = &'i Ident>,
+        tokens: proc_macro2::TokenStream,
+    ) -> Self {
+        let mut items = Self::default();
+        items.push(idents, tokens);
+        items
+    }
+
+    /// Adds `tokens`, which define the items named `idents`
+    fn push<'i>(
+        &mut self,
+        idents: impl IntoIterator<Item = &'i Ident>,
+        tokens: proc_macro2::TokenStream,
+    ) {
+        self.names.extend(idents.into_iter().map(Ident::to_string));
+        self.tokens.extend(tokens);
+    }
+
+    fn append(&mut self, other: Items) {
+        self.names.extend(other.names);
+        self.tokens.extend(other.tokens);
+    }
+}
+
+/// Emits an error for each name that is generated for multiple keys in
+/// `scope`, or that is one of its `reserved` names, since the items would
+/// collide with or shadow each other.
+pub fn check_method_names<'k>(
+    scope: &str,
+    key_method_names: impl IntoIterator<Item = (&'k str, Vec<String>)>,
+    reserved: &HashSet<&str>,
+) {
+    let mut seen = HashMap::new();
+
+    for (key_name, method_names) in key_method_names {
+        for method_name in method_names {
+            if reserved.contains(method_name.as_str()) {
+                emit_call_site_error!(
+                    "key `{}` generates `{}` in {}, which collides with an existing method; consider skipping it with `#[gen_settings_skip(key_name = \"{}\")]`",
+                    key_name,
+                    method_name,
+                    scope,
+                    key_name,
+                );
+                continue;
+            }
+
+            match seen.get(&method_name) {
+                Some(&other_key_name) if other_key_name != key_name => {
+                    emit_call_site_error!(
+                        "keys `{}` and `{}` both generate `{}` in {}; consider skipping one of them with `#[gen_settings_skip(key_name = \"{}\")]`",
+                        other_key_name,
+                        key_name,
+                        method_name,
+                        scope,
+                        key_name,
+                    );
+                }
+                Some(_) => (),
+                None => {
+                    seen.insert(method_name, key_name);
+                }
+            }
+        }
+    }
+}
+
 pub enum GetResult<'a> {
     Some(KeyGenerator<'a>),
     Skip,
@@ -31,46 +346,121 @@ pub struct KeyGenerators<'a> {
     key_names: HashMap<String, Context>,
     enums: HashMap<String, &'a SchemaEnum>,
     flags: HashMap<String, &'a SchemaFlag>,
+    forced_signatures: HashSet<SchemaKeySignature>,
     signature_skips: HashSet<SchemaKeySignature>,
     key_name_skips: HashSet<String>,
+    kind_skips: HashSet<ContainerKind>,
+    enum_names: HashMap<String, String>,
+    doc_hidden_keys: HashSet<String>,
+    crates: Crates,
+    field: &'a Field,
+    string_array_into_iter: bool,
+    string_array_iter: bool,
+    gstring_getters: bool,
+    must_use_getters: bool,
+    connect_changed_with_old_value: bool,
+    connect_changed_debounced: bool,
+    try_getters: bool,
+    gtk_crate: Option<syn::Path>,
 }
 
 impl<'a> KeyGenerators<'a> {
+    /// Built in contexts are derived from the key's type signature, see
+    /// [`signature::context`].
     pub fn with_defaults(
         enums: HashMap<String, &'a SchemaEnum>,
         flags: HashMap<String, &'a SchemaFlag>,
+        crates: Crates,
+        field: &'a Field,
     ) -> Self {
-        let mut this = Self {
+        Self {
             signatures: HashMap::new(),
             key_names: HashMap::new(),
             enums,
             flags,
+            forced_signatures: HashSet::new(),
             signature_skips: HashSet::new(),
             key_name_skips: HashSet::new(),
-        };
+            kind_skips: HashSet::new(),
+            enum_names: HashMap::new(),
+            doc_hidden_keys: HashSet::new(),
+            crates,
+            field,
+            string_array_into_iter: false,
+            string_array_iter: false,
+            gstring_getters: false,
+            must_use_getters: false,
+            connect_changed_with_old_value: false,
+            connect_changed_debounced: false,
+            try_getters: false,
+            gtk_crate: None,
+        }
+    }
+
+    /// Annotate the getters of all keys with `#[must_use]`
+    pub fn enable_must_use_getters(&mut self) {
+        self.must_use_getters = true;
+    }
 
-        // Built ins
-        this.insert_type("b", Context::new("bool"));
-        this.insert_type("i", Context::new("i32"));
-        this.insert_type("u", Context::new("u32"));
-        this.insert_type("x", Context::new("i64"));
-        this.insert_type("t", Context::new("u64"));
-        this.insert_type("d", Context::new("f64"));
-        this.insert_type("(ii)", Context::new("(i32, i32)"));
-        this.insert_type("as", Context::new_dissimilar("&[&str]", "Vec<String>"));
+    /// Generate a variant of the changed signal connector of all keys that also
+    /// passes the previous value
+    pub fn enable_connect_changed_with_old_value(&mut self) {
+        self.connect_changed_with_old_value = true;
+    }
+
+    /// Generate a variant of the changed signal connector of all keys that
+    /// coalesces bursts of changes into a single call
+    pub fn enable_connect_changed_debounced(&mut self) {
+        self.connect_changed_debounced = true;
+    }
 
-        this
+    /// Generate getters of all keys that return an error instead of panicking
+    /// if the stored value can't be converted
+    pub fn enable_try_getters(&mut self) {
+        self.try_getters = true;
+    }
+
+    /// Generate GTK expressions of all keys, using the given path to the `gtk` crate
+    pub fn enable_gtk_expressions(&mut self, gtk_crate: syn::Path) {
+        self.gtk_crate = Some(gtk_crate);
+    }
+
+    /// Make the setters of keys with type `as` accept any iterator of strings,
+    /// instead of only `&[&str]`
+    pub fn enable_string_array_into_iter(&mut self) {
+        self.string_array_into_iter = true;
+    }
+
+    /// Generate a getter returning an iterator over the strings of keys with
+    /// type `as`
+    pub fn enable_string_array_iter(&mut self) {
+        self.string_array_iter = true;
+    }
+
+    /// Make the getters of keys with type `s` or `as` return `glib::GString`
+    /// instead of `String`
+    pub fn enable_gstring_getters(&mut self) {
+        self.gstring_getters = true;
     }
 
     /// Add contexts that has higher priority than default, but lower than
     /// key_name overrides
+    ///
+    /// Defines are not applied on keys with choices, unless forced.
     pub fn add_signature_overrides(
         &mut self,
         overrides: HashMap<SchemaKeySignature, OverrideType>,
     ) {
         for (signature, item) in overrides {
             match item {
-                OverrideType::Define { arg_type, ret_type } => {
+                OverrideType::Define {
+                    arg_type,
+                    ret_type,
+                    force,
+                } => {
+                    if force {
+                        self.forced_signatures.insert(signature.clone());
+                    }
                     self.signatures
                         .insert(signature, Context::new_dissimilar(&arg_type, &ret_type));
                 }
@@ -81,11 +471,30 @@ impl<'a> KeyGenerators<'a> {
         }
     }
 
+    /// Name the enum or flags type generated for the keys with the given names
+    /// with the given identifiers, instead of the key name in pascal case
+    pub fn add_enum_name_overrides(&mut self, overrides: HashMap<String, String>) {
+        self.enum_names.extend(overrides);
+    }
+
+    /// Hide the generated methods of the keys with the given names from the docs
+    pub fn add_doc_hidden_keys(&mut self, key_names: HashSet<String>) {
+        self.doc_hidden_keys.extend(key_names);
+    }
+
+    /// Skip the keys of the given container kinds, unless they are overridden
+    /// by key name
+    pub fn add_kind_skips(&mut self, kinds: HashSet<ContainerKind>) {
+        self.kind_skips.extend(kinds);
+    }
+
     /// Add contexts that has higher priority than both default and signature overrides.
     pub fn add_key_name_overrides(&mut self, overrides: HashMap<String, OverrideType>) {
         for (key_name, item) in overrides {
             match item {
-                OverrideType::Define { arg_type, ret_type } => {
+                OverrideType::Define {
+                    arg_type, ret_type, ..
+                } => {
                     self.key_names
                         .insert(key_name, Context::new_dissimilar(&arg_type, &ret_type));
                 }
@@ -100,8 +509,29 @@ impl<'a> KeyGenerators<'a> {
         &'a self,
         key: &'a SchemaKey,
         aux_visibility: syn::Visibility,
+    ) -> Option<GetResult<'a>> {
+        Some(match self.generator(key, aux_visibility)? {
+            GetResult::Some(mut generator) => {
+                generator.must_use_getters = self.must_use_getters;
+                generator.connect_changed_with_old_value = self.connect_changed_with_old_value;
+                generator.connect_changed_debounced = self.connect_changed_debounced;
+                generator.try_getters = self.try_getters;
+                generator.gtk_crate = self.gtk_crate.as_ref();
+                generator.doc_hidden = self.doc_hidden_keys.contains(&key.name);
+                GetResult::Some(generator)
+            }
+            other => other,
+        })
+    }
+
+    fn generator(
+        &'a self,
+        key: &'a SchemaKey,
+        aux_visibility: syn::Visibility,
     ) -> Option<GetResult<'a>> {
         let key_signature = key.signature()?;
+        let crates = &self.crates;
+        let field = self.field;
 
         if self.key_name_skips.contains(&key.name) {
             return Some(GetResult::Skip);
@@ -112,44 +542,131 @@ impl<'a> KeyGenerators<'a> {
         }
 
         if let Some(context) = self.key_names.get(&key.name) {
-            return Some(GetResult::Some(KeyGenerator::new(key, context.clone())));
+            return Some(GetResult::Some(KeyGenerator::new(
+                key,
+                context.clone(),
+                crates,
+                field,
+            )));
+        }
+
+        if ContainerKind::of(&key_signature).is_some_and(|kind| self.kind_skips.contains(&kind)) {
+            return Some(GetResult::Skip);
         }
 
         if let Some(context) = self.signatures.get(&key_signature) {
-            return Some(GetResult::Some(KeyGenerator::new(key, context.clone())));
+            if key.choices.is_none() || self.forced_signatures.contains(&key_signature) {
+                return Some(GetResult::Some(KeyGenerator::new(
+                    key,
+                    context.clone(),
+                    crates,
+                    field,
+                )));
+            }
         }
 
+        // The name of the generated enum or flags type, if any
+        let enum_name = self
+            .enum_names
+            .get(&key.name)
+            .cloned()
+            .unwrap_or_else(|| key.name.to_pascal_case());
+
         Some(match key_signature {
             SchemaKeySignature::Type(type_) => match type_.as_str() {
-                "s" => GetResult::Some(string::key_generator(key, aux_visibility)),
-                _ => GetResult::Unknown,
+                "s" => GetResult::Some(string::key_generator(
+                    key,
+                    &enum_name,
+                    aux_visibility,
+                    crates,
+                    field,
+                    self.gstring_getters,
+                )),
+                "as" => {
+                    let context = if self.string_array_into_iter {
+                        let glib = &crates.glib;
+                        Context::new_dissimilar(
+                            "impl IntoIterator<Item = impl AsRef<str>>",
+                            "Vec<String>",
+                        )
+                        .with_to_variant(quote! {
+                            #glib::Variant::array_from_iter_with_type(
+                                #glib::VariantTy::STRING,
+                                value.into_iter().map(|item| #glib::ToVariant::to_variant(item.as_ref())),
+                            )
+                        })
+                    } else {
+                        signature::context(&type_, crates).unwrap()
+                    };
+                    let context = if self.gstring_getters {
+                        let glib = &crates.glib;
+                        let ret_type = format!("Vec<{}::GString>", glib.to_token_stream());
+                        Context {
+                            ret_type,
+                            ..context
+                        }
+                        .with_ret_conversions(
+                            quote! {
+                                #glib::Variant::array_iter_str(variant)
+                                    .ok()
+                                    .map(|iter| iter.map(#glib::GString::from).collect())
+                            },
+                            quote! {
+                                #glib::ToVariant::to_variant(
+                                    &value.iter().map(#glib::GString::as_str).collect::<Vec<_>>(),
+                                )
+                            },
+                        )
+                    } else {
+                        context
+                    };
+                    let generator = KeyGenerator::new(key, context, crates, field);
+                    GetResult::Some(if self.string_array_iter {
+                        generator.with_extra_methods(string_array_iter_items(key, crates, field))
+                    } else {
+                        generator
+                    })
+                }
+                _ => signature::context(&type_, crates).map_or(GetResult::Unknown, |context| {
+                    GetResult::Some(KeyGenerator::new(key, context, crates, field))
+                }),
             },
-            SchemaKeySignature::Enum(ref enum_name) => GetResult::Some(enumeration::key_generator(
+            SchemaKeySignature::Enum(ref enum_id) => GetResult::Some(enumeration::key_generator(
                 key,
-                self.enums.get(enum_name).unwrap_or_else(|| {
-                    abort_call_site!("expected an enum definition for `{}`", enum_name)
+                &enum_name,
+                self.enums.get(enum_id).unwrap_or_else(|| {
+                    abort_call_site!("expected an enum definition for `{}`", enum_id)
                 }),
                 aux_visibility,
+                crates,
+                field,
             )),
-            SchemaKeySignature::Flag(ref flag_name) => GetResult::Some(bitflag::key_generator(
+            SchemaKeySignature::Flag(ref flag_id) => GetResult::Some(bitflag::key_generator(
                 key,
-                self.flags.get(flag_name).unwrap_or_else(|| {
-                    abort_call_site!("expected a flag definition for `{}`", flag_name)
+                &enum_name,
+                self.flags.get(flag_id).unwrap_or_else(|| {
+                    abort_call_site!("expected a flag definition for `{}`", flag_id)
                 }),
                 aux_visibility,
+                crates,
+                field,
             )),
         })
     }
-
-    fn insert_type(&mut self, signature: &str, context: Context) {
-        self.signatures
-            .insert(SchemaKeySignature::Type(signature.to_string()), context);
-    }
 }
 
 pub struct KeyGenerator<'a> {
     key: &'a SchemaKey,
     context: Context,
+    crates: &'a Crates,
+    field: &'a Field,
+    extra_methods: Option<Box<Items>>,
+    must_use_getters: bool,
+    connect_changed_with_old_value: bool,
+    connect_changed_debounced: bool,
+    try_getters: bool,
+    gtk_crate: Option<&'a syn::Path>,
+    doc_hidden: bool,
 }
 
 impl<'a> KeyGenerator<'a> {
@@ -157,29 +674,449 @@ impl<'a> KeyGenerator<'a> {
         self.context.auxiliary.clone()
     }
 
-    fn new(key: &'a SchemaKey, context: Context) -> Self {
-        Self { key, context }
+    /// The name of the type defined by the auxiliary items, if any
+    pub fn auxiliary_type_name(&self) -> Option<&str> {
+        self.context
+            .auxiliary
+            .as_ref()
+            .map(|_| self.context.ret_type.as_str())
+    }
+
+    fn new(key: &'a SchemaKey, context: Context, crates: &'a Crates, field: &'a Field) -> Self {
+        Self {
+            key,
+            context,
+            crates,
+            field,
+            extra_methods: None,
+            must_use_getters: false,
+            connect_changed_with_old_value: false,
+            connect_changed_debounced: false,
+            try_getters: false,
+            gtk_crate: None,
+            doc_hidden: false,
+        }
+    }
+
+    /// Whether the generated methods are hidden from the docs
+    /// The return type of the getter
+    fn get_type(&self) -> syn::Type {
+        syn::parse_str(&self.context.ret_type).expect("types are validated before generating")
+    }
+
+    /// The parameter type of the setter
+    fn set_type(&self) -> syn::Type {
+        syn::parse_str(&self.context.arg_type).expect("types are validated before generating")
+    }
+
+    pub fn is_doc_hidden(&self) -> bool {
+        self.doc_hidden
+    }
+
+    /// Methods specific to the key's kind, emitted along with the common ones
+    fn with_extra_methods(mut self, extra_methods: Items) -> Self {
+        self.extra_methods = Some(Box::new(extra_methods));
+        self
+    }
+
+    /// Whether the key has a tuple type
+    fn is_tuple(&self) -> bool {
+        matches!(self.key.signature(), Some(SchemaKeySignature::Type(ref type_)) if type_.starts_with('('))
+    }
+
+    /// The types of the components of the key, along with the types they are
+    /// held as in a property, if it is a tuple of two basic types
+    fn pair_component_types(&self) -> Option<[(syn::Type, syn::Type); 2]> {
+        let Some(SchemaKeySignature::Type(type_)) = self.key.signature() else {
+            return None;
+        };
+
+        let component_types = |c: char| {
+            let (stored_type, widened_type) = value_types(&c.to_string(), self.crates)?;
+            let value_type = widened_type.unwrap_or_else(|| stored_type.clone());
+            Some((stored_type, value_type))
+        };
+
+        match type_
+            .strip_prefix('(')?
+            .strip_suffix(')')?
+            .chars()
+            .collect::<Vec<_>>()[..]
+        {
+            [first, second] => Some([component_types(first)?, component_types(second)?]),
+            _ => None,
+        }
+    }
+
+    /// Whether the key has a basic type, or an enum, which is stored as a string
+    fn is_scalar(&self) -> bool {
+        match self.key.signature() {
+            Some(SchemaKeySignature::Type(type_)) => {
+                matches!(
+                    type_.as_str(),
+                    "b" | "y" | "n" | "q" | "i" | "u" | "x" | "t" | "d" | "s"
+                )
+            }
+            Some(SchemaKeySignature::Enum(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the key can be represented by a property action, which are
+    /// only meaningful for booleans and enums
+    fn supports_property_action(&self) -> bool {
+        match self.key.signature() {
+            Some(SchemaKeySignature::Type(type_)) => {
+                type_ == "b" || (type_ == "s" && self.key.choices.is_some())
+            }
+            Some(SchemaKeySignature::Enum(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// The bounds of the key's range as typed literals, along with their type,
+    /// if the key is numerical and has both bounds specified
+    fn range_bounds(
+        &self,
+    ) -> Option<(
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+    )> {
+        fn parse_bounds<T: FromStr + quote::ToTokens>(
+            key: &SchemaKey,
+            min: &str,
+            max: &str,
+        ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+            let parse = |bound: &str| {
+                let value = bound.parse::<T>().unwrap_or_else(|_| {
+                    abort_call_site!("invalid range bound `{}` for key `{}`", bound, key.name)
+                });
+                quote! { #value }
+            };
+            (parse(min), parse(max))
+        }
+
+        let range = self.key.range.as_ref()?;
+        let min = range.min.as_deref()?.trim();
+        let max = range.max.as_deref()?.trim();
+
+        let Some(SchemaKeySignature::Type(type_)) = self.key.signature() else {
+            return None;
+        };
+
+        let ((min, max), type_) = match type_.as_str() {
+            "y" => (parse_bounds::<u8>(self.key, min, max), quote! { u8 }),
+            "n" => (parse_bounds::<i16>(self.key, min, max), quote! { i16 }),
+            "q" => (parse_bounds::<u16>(self.key, min, max), quote! { u16 }),
+            "i" => (parse_bounds::<i32>(self.key, min, max), quote! { i32 }),
+            "u" => (parse_bounds::<u32>(self.key, min, max), quote! { u32 }),
+            "x" => (parse_bounds::<i64>(self.key, min, max), quote! { i64 }),
+            "t" => (parse_bounds::<u64>(self.key, min, max), quote! { u64 }),
+            "d" => (parse_bounds::<f64>(self.key, min, max), quote! { f64 }),
+            _ => return None,
+        };
+        Some((min, max, type_))
+    }
+
+    /// The declarations of the getter, setter, and reset methods of the key in
+    /// the accessor trait, and their implementations delegating to the
+    /// inherent methods.
+    pub fn accessor_trait_items(&self) -> (Items, proc_macro2::TokenStream) {
+        let getter_func_ident = key_func_ident(&self.key.name);
+        let setter_func_ident = format_ident!("set_{}", getter_func_ident);
+        let reset_func_ident = format_ident!("reset_{}", getter_func_ident);
+
+        let get_type = self.get_type();
+        let set_type = self.set_type();
+
+        let func_docs = self.func_docs();
+
+        let idents = [&getter_func_ident, &setter_func_ident, &reset_func_ident];
+        let declarations = Items::new(
+            idents,
+            quote! {
+                #func_docs
+                fn #getter_func_ident(&self) -> #get_type;
+
+                #func_docs
+                fn #setter_func_ident(&self, value: #set_type);
+
+                #func_docs
+                fn #reset_func_ident(&self);
+            },
+        );
+        let implementations = quote! {
+            fn #getter_func_ident(&self) -> #get_type {
+                Self::#getter_func_ident(self)
+            }
+
+            fn #setter_func_ident(&self, value: #set_type) {
+                Self::#setter_func_ident(self, value)
+            }
+
+            fn #reset_func_ident(&self) {
+                Self::#reset_func_ident(self)
+            }
+        };
+        (declarations, implementations)
+    }
+
+    /// The field of the key in the values struct, the initializer of the field
+    /// reading the key, and the statement writing the field to `settings`, which
+    /// collects the name of the key in `unwritable_keys` if it can't be written.
+    /// The field is only written if it differs from the current value of the
+    /// key, and the key is reset instead if the field is its default value, so
+    /// it keeps following the default of the schema.
+    pub fn values_struct_items(
+        &self,
+    ) -> (
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+    ) {
+        let gio = &self.crates.gio;
+        let key_name = self.key.name.as_str();
+        let field_ident = key_func_ident(key_name);
+
+        let get_type = self.get_type();
+
+        let func_docs = self.func_docs();
+
+        let values_to_variant = self.ret_to_variant(quote! { &values.#field_ident });
+
+        let field = quote! {
+            #func_docs
+            pub #field_ident: #get_type,
+        };
+        let init = quote! {
+            #field_ident: self.#field_ident(),
+        };
+        let write = quote! {
+            if self.#field_ident() != values.#field_ident {
+                let variant = #values_to_variant;
+                let is_default = #gio::prelude::SettingsExt::default_value(&settings, #key_name)
+                    .is_some_and(|default_value| default_value == variant);
+                let is_written = #gio::prelude::SettingsExt::is_writable(&settings, #key_name)
+                    && if is_default {
+                        #gio::prelude::SettingsExt::reset(&settings, #key_name);
+                        true
+                    } else {
+                        #gio::prelude::SettingsExt::set_value(&settings, #key_name, &variant).is_ok()
+                    };
+                if !is_written {
+                    unwritable_keys.push(#key_name);
+                }
+            }
+        };
+        (field, init, write)
+    }
+
+    /// Free functions taking a `&gio::Settings`, which delegate to the methods of
+    /// the struct with the given name in the parent module
+    pub fn free_function_items(&self, struct_ident: &Ident) -> Items {
+        let Crates { gio, glib } = self.crates;
+        let key_name = self.key.name.as_str();
+
+        let getter_func_ident = key_func_ident(key_name);
+        let static_getter_func_ident = format_ident!("get_{}", getter_func_ident);
+        let setter_func_ident = format_ident!("set_{}", getter_func_ident);
+        let try_setter_func_ident = format_ident!("try_set_{}", getter_func_ident);
+        let reset_func_ident = format_ident!("reset_{}", getter_func_ident);
+        let connect_changed_func_ident = format_ident!("connect_{}_changed", getter_func_ident);
+
+        let get_type = self.get_type();
+        let set_type = self.set_type();
+
+        let must_use = if self.must_use_getters {
+            quote! { #[must_use] }
+        } else {
+            quote! {}
+        };
+
+        let func_docs = self.func_docs();
+
+        let idents = [
+            &getter_func_ident,
+            &setter_func_ident,
+            &try_setter_func_ident,
+            &reset_func_ident,
+            &connect_changed_func_ident,
+        ];
+        Items::new(
+            idents,
+            quote! {
+                #func_docs
+                #must_use
+                pub fn #getter_func_ident(settings: &#gio::Settings) -> #get_type {
+                    super::#struct_ident::#static_getter_func_ident(settings)
+                }
+
+                #func_docs
+                pub fn #setter_func_ident(settings: &#gio::Settings, value: #set_type) {
+                    super::#struct_ident::from_settings(#gio::Settings::clone(settings))
+                        .#setter_func_ident(value)
+                }
+
+                #func_docs
+                pub fn #try_setter_func_ident(settings: &#gio::Settings, value: #set_type) -> std::result::Result<(), #glib::BoolError> {
+                    super::#struct_ident::from_settings(#gio::Settings::clone(settings))
+                        .#try_setter_func_ident(value)
+                }
+
+                #func_docs
+                pub fn #reset_func_ident(settings: &#gio::Settings) {
+                    super::#struct_ident::from_settings(#gio::Settings::clone(settings))
+                        .#reset_func_ident()
+                }
+
+                #func_docs
+                pub fn #connect_changed_func_ident(settings: &#gio::Settings, f: impl Fn(&#gio::Settings) + 'static) -> #glib::SignalHandlerId {
+                    #gio::prelude::SettingsExt::connect_changed(settings, Some(#key_name), move |settings, _| {
+                        f(settings)
+                    })
+                }
+            },
+        )
+    }
+
+    /// The methods of the extension trait on `gio::Settings`, which call the
+    /// methods of `gio::Settings` directly, except for the getter, which
+    /// delegates to the static getter of the struct with the given name
+    pub fn settings_ext_items(&self, struct_ident: &Ident) -> Items {
+        let Crates { gio, glib } = self.crates;
+        let key_name = self.key.name.as_str();
+
+        let getter_func_ident = key_func_ident(key_name);
+        let static_getter_func_ident = format_ident!("get_{}", getter_func_ident);
+        let setter_func_ident = format_ident!("set_{}", getter_func_ident);
+        let try_setter_func_ident = format_ident!("try_set_{}", getter_func_ident);
+        let reset_func_ident = format_ident!("reset_{}", getter_func_ident);
+        let connect_changed_func_ident = format_ident!("connect_{}_changed", getter_func_ident);
+
+        let get_type = self.get_type();
+        let set_type = self.set_type();
+
+        let must_use = if self.must_use_getters {
+            quote! { #[must_use] }
+        } else {
+            quote! {}
+        };
+
+        let func_docs = self.func_docs();
+        let settings = quote! { #glib::object::Cast::upcast_ref::<#gio::Settings>(self) };
+        let to_variant = self
+            .context
+            .to_variant
+            .clone()
+            .unwrap_or_else(|| quote! { #glib::ToVariant::to_variant(&value) });
+
+        let idents = [
+            &getter_func_ident,
+            &setter_func_ident,
+            &try_setter_func_ident,
+            &reset_func_ident,
+            &connect_changed_func_ident,
+        ];
+        Items::new(
+            idents,
+            quote! {
+                #func_docs
+                #must_use
+                fn #getter_func_ident(&self) -> #get_type {
+                    #struct_ident::#static_getter_func_ident(#settings)
+                }
+
+                #func_docs
+                fn #setter_func_ident(&self, value: #set_type) {
+                    Self::#try_setter_func_ident(self, value).unwrap_or_else(|err| panic!("failed to set value for key `{}`: {:?}", #key_name, err))
+                }
+
+                #func_docs
+                fn #try_setter_func_ident(&self, value: #set_type) -> std::result::Result<(), #glib::BoolError> {
+                    #gio::prelude::SettingsExtManual::set(#settings, #key_name, #to_variant)
+                }
+
+                #func_docs
+                fn #reset_func_ident(&self) {
+                    #gio::prelude::SettingsExt::reset(#settings, #key_name);
+                }
+
+                #func_docs
+                fn #connect_changed_func_ident(&self, f: impl Fn(&#gio::Settings) + 'static) -> #glib::SignalHandlerId {
+                    #gio::prelude::SettingsExt::connect_changed(#settings, Some(#key_name), move |settings, _| {
+                        f(settings)
+                    })
+                }
+            },
+        )
+    }
+
+    /// An expression converting the `&Variant` expression to an `Option` of the
+    /// return type
+    fn variant_to_ret(&self, variant: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let glib = &self.crates.glib;
+        let get_type = self.get_type();
+
+        match self.context.ret_conversions {
+            Some(ref conversions) => {
+                let from_variant = &conversions.from_variant;
+                quote! {
+                    {
+                        let variant: &#glib::Variant = #variant;
+                        #from_variant
+                    }
+                }
+            }
+            None => quote! { #glib::Variant::get::<#get_type>(#variant) },
+        }
+    }
+
+    /// An expression converting the expression, a reference to the return type,
+    /// to a variant
+    fn ret_to_variant(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let glib = &self.crates.glib;
+        let get_type = self.get_type();
+
+        match self.context.ret_conversions {
+            Some(ref conversions) => {
+                let to_variant = &conversions.to_variant;
+                quote! {
+                    {
+                        let value: &#get_type = #value;
+                        #to_variant
+                    }
+                }
+            }
+            None => quote! { #glib::ToVariant::to_variant(#value) },
+        }
     }
 
     fn func_docs(&self) -> proc_macro2::TokenStream {
         let mut stream = proc_macro2::TokenStream::new();
 
+        if self.doc_hidden {
+            stream.extend(quote! {
+                #[doc(hidden)]
+            });
+        }
+
         let has_summary = self
             .key
             .summary
             .as_ref()
-            .map_or(false, |summary| !summary.is_empty());
+            .is_some_and(|summary| !summary.is_empty());
 
         let has_description = self
             .key
             .description
             .as_ref()
-            .map_or(false, |description| !description.is_empty());
+            .is_some_and(|description| !description.is_empty());
 
         if has_summary {
-            let summary = self.key.summary.as_ref().unwrap();
+            let summary_lines = doc_lines(self.key.summary.as_ref().unwrap());
             stream.extend(quote! {
-                #[doc = #summary]
+                #(#[doc = #summary_lines])*
             });
         }
 
@@ -190,13 +1127,44 @@ impl<'a> KeyGenerator<'a> {
         }
 
         if has_description {
-            let description = self.key.description.as_ref().unwrap();
+            let description_lines = doc_lines(self.key.description.as_ref().unwrap());
             stream.extend(quote! {
-                #[doc = #description]
+                #(#[doc = #description_lines])*
             });
         }
 
-        let default_docs = format!("default: {}", self.key.default);
+        let signature_docs = match self.key.signature() {
+            Some(SchemaKeySignature::Type(type_)) => format!("type `{}`", type_),
+            Some(SchemaKeySignature::Enum(enum_id)) => format!("enum `{}`", enum_id),
+            Some(SchemaKeySignature::Flag(flag_id)) => format!("flags `{}`", flag_id),
+            None => unreachable!("key signature must have been validated"),
+        };
+        let key_docs = format!(
+            "Wraps GSettings key `{}` ({})",
+            self.key.name, signature_docs
+        );
+        if has_summary || has_description {
+            stream.extend(quote! {
+                #[doc = ""]
+            });
+        }
+        stream.extend(quote! {
+            #[doc = #key_docs]
+        });
+
+        let default_value = self.key.normalized_default_value();
+        let is_container = matches!(
+            self.key.signature(),
+            Some(SchemaKeySignature::Type(ref type_)) if type_.starts_with(['a', 'm', '(', '{', 'v'])
+        );
+        let default_docs = if is_container {
+            // Container defaults may span multiple lines and contain characters
+            // that rustdoc would interpret, so they are kept as is in inline code
+            let default_value = default_value.split_whitespace().collect::<Vec<_>>();
+            format!("default: `{}`", default_value.join(" "))
+        } else {
+            format!("default: {}", default_value)
+        };
         stream.extend(quote! {
             #[doc = ""]
             #[doc = #default_docs]
@@ -204,8 +1172,8 @@ impl<'a> KeyGenerator<'a> {
 
         // only needed for numerical types
         if let Some(ref range) = self.key.range {
-            let has_min = range.min.as_ref().map_or(false, |min| !min.is_empty());
-            let has_max = range.max.as_ref().map_or(false, |max| !max.is_empty());
+            let has_min = range.min.as_ref().is_some_and(|min| !min.is_empty());
+            let has_max = range.max.as_ref().is_some_and(|max| !max.is_empty());
 
             if has_min || has_max {
                 stream.extend(quote! {
@@ -232,81 +1200,637 @@ impl<'a> KeyGenerator<'a> {
     }
 }
 
-impl quote::ToTokens for KeyGenerator<'_> {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+impl KeyGenerator<'_> {
+    /// The constants and methods of the key in the impl block of the struct,
+    /// including the ones specific to its kind
+    pub fn impl_items(&self) -> Items {
+        let mut items = Items::default();
+        let Crates { gio, glib } = self.crates;
+        let settings = self.field.access();
         let key_name = self.key.name.as_str();
-        let key_name_snake_case = key_name.to_snake_case();
-        let getter_func_ident = Ident::new(&key_name_snake_case, Span::call_site());
+        let getter_func_ident = key_func_ident(key_name);
 
         let connect_changed_func_ident = format_ident!("connect_{}_changed", getter_func_ident);
+        let connect_changed_weak_func_ident =
+            format_ident!("connect_{}_changed_weak", getter_func_ident);
         let bind_func_ident = format_ident!("bind_{}", getter_func_ident);
         let create_action_func_ident = format_ident!("create_{}_action", getter_func_ident);
         let reset_func_ident = format_ident!("reset_{}", getter_func_ident);
+        let connect_reset_func_ident = format_ident!("connect_{}_reset", getter_func_ident);
+
+        let action_name_const_ident = format_ident!(
+            "{}_ACTION_NAME",
+            getter_func_ident.to_string().to_uppercase()
+        );
+        let signature_const_ident =
+            format_ident!("SIGNATURE_{}", getter_func_ident.to_string().to_uppercase());
+        let signature = self
+            .key
+            .signature()
+            .expect("signatures are validated before generating")
+            .storage_type()
+            .to_string();
+
+        let construct_self = self
+            .field
+            .construct(quote! { #gio::Settings::clone(settings) });
 
         let func_docs = self.func_docs();
+        let action_name_docs = format!(
+            "The name of the action created by `create_{}_action`.",
+            getter_func_ident
+        );
+        let signature_docs = format!("The type signature that `{}` is stored as.", key_name);
+
+        // The callback of flags keys also gets the value, as the parsed bitflags
+        // are easier to inspect than the stored array of nicks
+        let connect_changed = if matches!(self.key.signature(), Some(SchemaKeySignature::Flag(_))) {
+            let get_type = self.get_type();
+            quote! {
+                #func_docs
+                pub fn #connect_changed_func_ident(&self, f: impl Fn(&Self, #get_type) + 'static) -> #glib::SignalHandlerId {
+                    #gio::prelude::SettingsExt::connect_changed(&#settings, Some(#key_name), move |settings, _| {
+                        let this = #construct_self;
+                        let value = this.#getter_func_ident();
+                        f(&this, value)
+                    })
+                }
+            }
+        } else {
+            quote! {
+                #func_docs
+                pub fn #connect_changed_func_ident(&self, f: impl Fn(&Self) + 'static) -> #glib::SignalHandlerId {
+                    #gio::prelude::SettingsExt::connect_changed(&#settings, Some(#key_name), move |settings, _| {
+                        f(&#construct_self)
+                    })
+                }
+            }
+        };
+
+        let idents = [
+            &action_name_const_ident,
+            &signature_const_ident,
+            &connect_changed_func_ident,
+            &connect_changed_weak_func_ident,
+            &connect_reset_func_ident,
+            &create_action_func_ident,
+            &reset_func_ident,
+        ];
+        items.push(idents, quote! {
+            #[doc = #action_name_docs]
+            pub const #action_name_const_ident: &'static str = #key_name;
+
+            #[doc = #signature_docs]
+            pub const #signature_const_ident: &'static str = #signature;
+
+            #connect_changed
 
-        tokens.extend(quote! {
             #func_docs
-            pub fn #connect_changed_func_ident(&self, f: impl Fn(&Self) + 'static) -> gio::glib::SignalHandlerId {
-                gio::prelude::SettingsExt::connect_changed(&self.0, Some(#key_name), move |settings, _| {
-                    f(&Self(gio::Settings::clone(settings)))
+            pub fn #connect_changed_weak_func_ident<O: #glib::ObjectType>(&self, object: &#glib::WeakRef<O>, f: impl Fn(&O, &Self) + 'static) -> #glib::SignalHandlerId {
+                let object = #glib::WeakRef::clone(object);
+                #gio::prelude::SettingsExt::connect_changed(&#settings, Some(#key_name), move |settings, _| {
+                    if let Some(object) = object.upgrade() {
+                        f(&object, &#construct_self)
+                    }
                 })
             }
 
             #func_docs
-            pub fn #bind_func_ident<'a>(&'a self, object: &'a impl gio::glib::object::IsA<gio::glib::Object>, property: &'a str) -> gio::BindingBuilder<'a> {
-                gio::prelude::SettingsExtManual::bind(&self.0, #key_name, object, property)
+            pub fn #connect_reset_func_ident(&self, f: impl Fn(&Self) + 'static) -> #glib::SignalHandlerId {
+                #gio::prelude::SettingsExt::connect_changed(&#settings, Some(#key_name), move |settings, _| {
+                    if #gio::prelude::SettingsExt::user_value(settings, #key_name).is_none() {
+                        f(&#construct_self)
+                    }
+                })
             }
 
             #func_docs
-            pub fn #create_action_func_ident(&self) -> gio::Action {
-                gio::prelude::SettingsExt::create_action(&self.0, #key_name)
+            pub fn #create_action_func_ident(&self) -> #gio::Action {
+                #gio::prelude::SettingsExt::create_action(&#settings, #key_name)
             }
 
             #func_docs
             pub fn #reset_func_ident(&self) {
-                gio::prelude::SettingsExt::reset(&self.0, #key_name);
+                #gio::prelude::SettingsExt::reset(&#settings, #key_name);
             }
         });
 
+        // The default mapping of `gio::Settings::bind` does not support tuples,
+        // even for variant properties, so those can only be bound with a mapping
+        if !self.is_tuple() {
+            items.push([&bind_func_ident], quote! {
+                #func_docs
+                pub fn #bind_func_ident<'a>(&'a self, object: &'a impl #glib::object::IsA<#glib::Object>, property: &'a str) -> #gio::BindingBuilder<'a> {
+                    #gio::prelude::SettingsExtManual::bind(&#settings, #key_name, object, property)
+                }
+            });
+        }
+
+        if let Some(component_types) = self.pair_component_types() {
+            let bind_components_func_ident = format_ident!("bind_{}_components", getter_func_ident);
+            let [(first_type, _), (second_type, _)] = &component_types;
+
+            // Each property is bound to one component of the tuple, so writing it
+            // has to keep the other component as currently stored
+            let bind_component = |object: &Ident, property: &Ident, index: syn::Index| {
+                let other_index = syn::Index::from(1 - index.index as usize);
+                let (component_type, value_type) = &component_types[index.index as usize];
+                let component = if index.index == 0 {
+                    quote! { (component, current.#other_index) }
+                } else {
+                    quote! { (current.#other_index, component) }
+                };
+
+                // Components without a value type of their own are widened, and
+                // written only if they fit
+                quote! {
+                    let settings = #gio::Settings::clone(&#settings);
+                    #gio::prelude::SettingsExtManual::bind(&#settings, #key_name, #object, #property)
+                        .mapping(|variant, _| {
+                            let value = #glib::Variant::get::<(#first_type, #second_type)>(variant)?;
+                            Some(#glib::value::ToValue::to_value(&#value_type::from(value.#index)))
+                        })
+                        .set_mapping(move |value, _| {
+                            let component = #component_type::try_from(value.get::<#value_type>().ok()?).ok()?;
+                            let current = #gio::prelude::SettingsExtManual::get::<(#first_type, #second_type)>(&settings, #key_name);
+                            Some(#glib::ToVariant::to_variant(&#component))
+                        })
+                        .build();
+                }
+            };
+            let bind_first = bind_component(
+                &format_ident!("first_object"),
+                &format_ident!("first_property"),
+                syn::Index::from(0),
+            );
+            let bind_second = bind_component(
+                &format_ident!("second_object"),
+                &format_ident!("second_property"),
+                syn::Index::from(1),
+            );
+
+            items.push(
+                [&bind_components_func_ident],
+                quote! {
+                    #func_docs
+                    pub fn #bind_components_func_ident(
+                        &self,
+                        first_object: &impl #glib::object::IsA<#glib::Object>,
+                        first_property: &str,
+                        second_object: &impl #glib::object::IsA<#glib::Object>,
+                        second_property: &str,
+                    ) {
+                        {
+                            #bind_first
+                        }
+                        {
+                            #bind_second
+                        }
+                    }
+                },
+            );
+        }
+
         let setter_func_ident = format_ident!("set_{}", getter_func_ident);
         let try_setter_func_ident = format_ident!("try_set_{}", getter_func_ident);
+        let bind_with_mapping_func_ident = format_ident!("bind_{}_with_mapping", getter_func_ident);
+        let replace_func_ident = format_ident!("replace_{}", getter_func_ident);
+        let static_getter_func_ident = format_ident!("get_{}", getter_func_ident);
+        let getter_or_func_ident = format_ident!("{}_or", getter_func_ident);
         let default_value_func_ident = format_ident!("{}_default_value", getter_func_ident);
+        let is_default_func_ident = format_ident!("{}_is_default", getter_func_ident);
+        let is_default_docs = format!(
+            "Returns whether the value of `{}` is equal to its default value.",
+            key_name
+        );
+
+        let get_type = self.get_type();
+        let set_type = self.set_type();
+
+        let must_use = if self.must_use_getters {
+            quote! { #[must_use] }
+        } else {
+            quote! {}
+        };
 
-        let get_type = syn::parse_str::<syn::Type>(&self.context.ret_type)
-            .unwrap_or_else(|_| panic!("Invalid type `{}`", &self.context.ret_type));
-        let set_type = syn::parse_str::<syn::Type>(&self.context.arg_type)
-            .unwrap_or_else(|_| panic!("Invalid type `{}`", &self.context.arg_type));
+        let to_variant = self
+            .context
+            .to_variant
+            .clone()
+            .unwrap_or_else(|| quote! { #glib::ToVariant::to_variant(&value) });
+
+        let mapping_from_variant = self.variant_to_ret(quote! { variant });
+        let mapping_to_variant = self.ret_to_variant(quote! { &value });
+        let static_getter_body = if self.context.ret_conversions.is_some() {
+            let from_variant = self
+                .variant_to_ret(quote! { &#gio::prelude::SettingsExt::value(settings, #key_name) });
+            quote! {
+                #from_variant.unwrap_or_else(|| panic!("failed to convert value for key `{}`", #key_name))
+            }
+        } else {
+            quote! { #gio::prelude::SettingsExtManual::get(settings, #key_name) }
+        };
+        let default_value_from_variant = self.variant_to_ret(
+            quote! { &#gio::prelude::SettingsExt::default_value(&#settings, #key_name).unwrap() },
+        );
 
-        tokens.extend(quote! {
+        let idents = [
+            &setter_func_ident,
+            &bind_with_mapping_func_ident,
+            &try_setter_func_ident,
+            &replace_func_ident,
+            &getter_func_ident,
+            &static_getter_func_ident,
+            &getter_or_func_ident,
+            &default_value_func_ident,
+            &is_default_func_ident,
+        ];
+        items.push(idents, quote! {
             #func_docs
             pub fn #setter_func_ident(&self, value: #set_type) {
                 self.#try_setter_func_ident(value).unwrap_or_else(|err| panic!("failed to set value for key `{}`: {:?}", #key_name, err))
             }
 
             #func_docs
-            pub fn #try_setter_func_ident(&self, value: #set_type) -> std::result::Result<(), gio::glib::BoolError> {
-                gio::prelude::SettingsExtManual::set(&self.0, #key_name, &value)
+            pub fn #bind_with_mapping_func_ident<'a, V: #glib::value::ToValue>(
+                &'a self,
+                object: &'a impl #glib::object::IsA<#glib::Object>,
+                property: &'a str,
+                get_mapping: impl Fn(#get_type) -> Option<V> + 'static,
+                set_mapping: impl Fn(&#glib::Value) -> Option<#get_type> + 'static,
+            ) -> #gio::BindingBuilder<'a> {
+                #gio::prelude::SettingsExtManual::bind(&#settings, #key_name, object, property)
+                    .mapping(move |variant, _| {
+                        let value = #mapping_from_variant?;
+                        get_mapping(value).map(|value| #glib::value::ToValue::to_value(&value))
+                    })
+                    .set_mapping(move |value, _| {
+                        set_mapping(value).map(|value| #mapping_to_variant)
+                    })
             }
 
             #func_docs
+            pub fn #try_setter_func_ident(&self, value: #set_type) -> std::result::Result<(), #glib::BoolError> {
+                #gio::prelude::SettingsExtManual::set(&#settings, #key_name, #to_variant)
+            }
+
+            #func_docs
+            pub fn #replace_func_ident(&self, value: #set_type) -> #get_type {
+                let old_value = self.#getter_func_ident();
+                self.#setter_func_ident(value);
+                old_value
+            }
+
+            #func_docs
+            #must_use
             pub fn #getter_func_ident(&self) -> #get_type {
-                gio::prelude::SettingsExtManual::get(&self.0, #key_name)
+                Self::#static_getter_func_ident(&#settings)
+            }
+
+            #func_docs
+            #must_use
+            pub fn #static_getter_func_ident(settings: &#gio::Settings) -> #get_type {
+                #static_getter_body
+            }
+
+            #func_docs
+            #must_use
+            pub fn #getter_or_func_ident(&self, fallback: #get_type) -> #get_type {
+                let has_key = #gio::prelude::SettingsExt::settings_schema(&#settings)
+                    .map_or(false, |schema| schema.has_key(#key_name));
+
+                if has_key {
+                    self.#getter_func_ident()
+                } else {
+                    fallback
+                }
             }
 
             #func_docs
             pub fn #default_value_func_ident(&self) -> #get_type {
-                gio::glib::Variant::get(&gio::prelude::SettingsExt::default_value(&self.0, #key_name).unwrap()).unwrap()
+                #default_value_from_variant.unwrap()
+            }
+
+            #[doc = #is_default_docs]
+            pub fn #is_default_func_ident(&self) -> bool {
+                #gio::prelude::SettingsExt::value(&#settings, #key_name) == #gio::prelude::SettingsExt::default_value(&#settings, #key_name).unwrap()
             }
         });
+
+        if self.try_getters {
+            let try_getter_func_ident = format_ident!("try_{}", getter_func_ident);
+            let try_from_variant = self.variant_to_ret(quote! { &variant });
+
+            items.push([&try_getter_func_ident], quote! {
+                #func_docs
+                #must_use
+                pub fn #try_getter_func_ident(&self) -> std::result::Result<#get_type, #glib::BoolError> {
+                    let variant = #gio::prelude::SettingsExt::value(&#settings, #key_name);
+                    #try_from_variant.ok_or_else(|| {
+                        #glib::bool_error!(
+                            "failed to convert value of type `{}` for key `{}`",
+                            variant.type_(),
+                            #key_name
+                        )
+                    })
+                }
+            });
+        }
+
+        if self.connect_changed_with_old_value {
+            let connect_changed_with_old_value_func_ident =
+                format_ident!("connect_{}_changed_with_old_value", getter_func_ident);
+
+            items.push([&connect_changed_with_old_value_func_ident], quote! {
+                #func_docs
+                pub fn #connect_changed_with_old_value_func_ident(&self, f: impl Fn(&Self, &#get_type, &#get_type) + 'static) -> #glib::SignalHandlerId {
+                    // gio doesn't provide the previous value, so it has to be cached
+                    let last_value = std::cell::RefCell::new(self.#getter_func_ident());
+                    #gio::prelude::SettingsExt::connect_changed(&#settings, Some(#key_name), move |settings, _| {
+                        let this = #construct_self;
+                        let value = this.#getter_func_ident();
+                        let old_value = last_value.replace(value.clone());
+                        f(&this, &old_value, &value);
+                    })
+                }
+            });
+        }
+
+        if self.connect_changed_debounced {
+            let connect_changed_debounced_func_ident =
+                format_ident!("connect_{}_changed_debounced", getter_func_ident);
+
+            items.push([&connect_changed_debounced_func_ident], quote! {
+                #func_docs
+                pub fn #connect_changed_debounced_func_ident(&self, duration: std::time::Duration, f: impl Fn(&Self, &#get_type) + 'static) -> #glib::SignalHandlerId {
+                    // Owned by the handler, so disconnecting it also cancels the pending call
+                    struct PendingSource(std::rc::Rc<std::cell::RefCell<Option<#glib::SourceId>>>);
+
+                    impl std::ops::Drop for PendingSource {
+                        fn drop(&mut self) {
+                            if let Some(source_id) = self.0.borrow_mut().take() {
+                                source_id.remove();
+                            }
+                        }
+                    }
+
+                    let f = std::rc::Rc::new(f);
+                    // Each change restarts the timeout, so it only fires after a quiet period
+                    let pending_source = PendingSource(std::rc::Rc::new(std::cell::RefCell::new(None)));
+                    #gio::prelude::SettingsExt::connect_changed(&#settings, Some(#key_name), move |settings, _| {
+                        let pending_source_id = &pending_source.0;
+                        if let Some(source_id) = pending_source_id.borrow_mut().take() {
+                            source_id.remove();
+                        }
+
+                        let this = #construct_self;
+                        let f = std::rc::Rc::clone(&f);
+                        let pending_source_id_clone = std::rc::Rc::clone(pending_source_id);
+                        let source_id = #glib::timeout_add_local_once(duration, move || {
+                            pending_source_id_clone.borrow_mut().take();
+                            let value = this.#getter_func_ident();
+                            f(&this, &value);
+                        });
+                        pending_source_id.borrow_mut().replace(source_id);
+                    })
+                }
+            });
+        }
+
+        if let Some(SchemaKeySignature::Type(ref type_)) = self.key.signature() {
+            if type_ == "b" {
+                let default = match self.key.default_value() {
+                    "true" => true,
+                    "false" => false,
+                    other => abort_call_site!(
+                        "invalid default value `{}` for boolean key `{}`",
+                        other,
+                        key_name
+                    ),
+                };
+                let const_default_func_ident = format_ident!("default_{}", getter_func_ident);
+                let bind_inverted_func_ident = format_ident!("bind_{}_inverted", getter_func_ident);
+
+                let idents = [&const_default_func_ident, &bind_inverted_func_ident];
+                items.push(idents, quote! {
+                    #func_docs
+                    pub const fn #const_default_func_ident() -> bool {
+                        #default
+                    }
+
+                    #func_docs
+                    pub fn #bind_inverted_func_ident<'a>(&'a self, object: &'a impl #glib::object::IsA<#glib::Object>, property: &'a str) -> #gio::BindingBuilder<'a> {
+                        #gio::prelude::SettingsExtManual::bind(&#settings, #key_name, object, property)
+                            .invert_boolean()
+                    }
+                });
+            }
+        }
+
+        if let Some((min, max, type_)) = self.range_bounds() {
+            let range_func_ident = format_ident!("{}_range", getter_func_ident);
+            let docs = format!(
+                "Returns the minimum and maximum values of `{}`, as specified in the schema.",
+                key_name
+            );
+
+            items.push(
+                [&range_func_ident],
+                quote! {
+                    #[doc = #docs]
+                    pub fn #range_func_ident(&self) -> (#type_, #type_) {
+                        (#min, #max)
+                    }
+                },
+            );
+        }
+
+        if let Some(gtk) = self.gtk_crate {
+            let expression_func_ident = format_ident!("{}_expression", getter_func_ident);
+            let docs = format!(
+                "Creates an expression that evaluates to the value of `{}` as a `glib::Variant`, \
+                which is updated when the key changes.",
+                key_name
+            );
+
+            // The state of the action created by `create_action` is kept in sync
+            // with the key, so it can be observed as a property.
+            items.push([&expression_func_ident], quote! {
+                #[doc = #docs]
+                pub fn #expression_func_ident(&self) -> #gtk::PropertyExpression {
+                    let action = #gio::prelude::SettingsExt::create_action(&#settings, #key_name);
+                    #gtk::PropertyExpression::new(
+                        <#gio::Action as #glib::StaticType>::static_type(),
+                        Some(#gtk::ConstantExpression::new(&action)),
+                        "state",
+                    )
+                }
+            });
+        }
+
+        if self.supports_property_action() {
+            let create_property_action_func_ident =
+                format_ident!("create_{}_property_action", getter_func_ident);
+            let docs = format!(
+                "Binds `{}` to `property` of `object`, and returns a `gio::PropertyAction` for that property, named after the key.\n\n\
+                The binding is kept even if the action is dropped, until `object` is finalized or \
+                `gio::Settings::unbind` is called with `object` and `property`.",
+                key_name
+            );
+
+            items.push([&create_property_action_func_ident], quote! {
+                #[doc = #docs]
+                pub fn #create_property_action_func_ident(&self, object: &impl #glib::object::IsA<#glib::Object>, property: &str) -> #gio::PropertyAction {
+                    #gio::prelude::SettingsExtManual::bind(&#settings, #key_name, object, property).build();
+                    #gio::PropertyAction::new(#key_name, object, property)
+                }
+            });
+        }
+
+        if self.is_scalar() {
+            let bind_to_action_func_ident = format_ident!("bind_{}_to_action", getter_func_ident);
+            let docs = format!(
+                "Syncs the state of `action` with `{}` in both directions.\n\n\
+                Returns the ids of the handlers of the `changed` signal of the settings and the \
+                `change-state` signal of the action, which can be disconnected to unbind them. \
+                A state that can't be written is logged and not applied.",
+                key_name
+            );
+
+            items.push([&bind_to_action_func_ident], quote! {
+                #[doc = #docs]
+                pub fn #bind_to_action_func_ident(&self, action: &#gio::SimpleAction) -> (#glib::SignalHandlerId, #glib::SignalHandlerId) {
+                    #gio::SimpleAction::set_state(action, &#gio::prelude::SettingsExt::value(&#settings, #key_name));
+
+                    let action_weak = #glib::prelude::ObjectExt::downgrade(action);
+                    let changed_handler_id = #gio::prelude::SettingsExt::connect_changed(&#settings, Some(#key_name), move |settings, _| {
+                        if let Some(action) = action_weak.upgrade() {
+                            #gio::SimpleAction::set_state(&action, &#gio::prelude::SettingsExt::value(settings, #key_name));
+                        }
+                    });
+
+                    // The state is only updated once the key is changed, so it is kept
+                    // as is if the value can't be written
+                    let settings = #gio::Settings::clone(&#settings);
+                    let change_state_handler_id = #gio::SimpleAction::connect_change_state(action, move |action, value| {
+                        if let Some(value) = value {
+                            if let Err(err) = #gio::prelude::SettingsExt::set_value(&settings, #key_name, value) {
+                                #glib::g_warning!(
+                                    "gsettings-macro",
+                                    "failed to set key `{}` from the state of action `{}`: {}",
+                                    #key_name,
+                                    #gio::prelude::ActionExt::name(action),
+                                    err
+                                );
+                            }
+                        }
+                    });
+
+                    (changed_handler_id, change_state_handler_id)
+                }
+            });
+        }
+
+        if let Some(ref extra_methods) = self.extra_methods {
+            items.append(Items::clone(extra_methods));
+        }
+
+        items
     }
 }
 
+/// Splits the text from the schema into lines that can be used as doc comments.
+///
+/// Each line is trimmed, so the indentation in the schema isn't rendered as a
+/// code block, and leading and trailing blank lines are removed. Characters
+/// that rustdoc would interpret as links or HTML tags are escaped.
+fn doc_lines(text: &str) -> Vec<String> {
+    let lines = text
+        .lines()
+        .map(|line| {
+            line.trim().chars().fold(String::new(), |mut escaped, c| {
+                if matches!(c, '[' | ']' | '<' | '>') {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+                escaped
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let start = lines.iter().position(|line| !line.is_empty()).unwrap_or(0);
+    let end = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(0, |index| index + 1);
+    lines[start..end.max(start)].to_vec()
+}
+
+/// Creates the identifier that the generated methods of the key is based on.
+///
+/// The key name is normalized to snake case, so `HttpProxy`, `httpProxy` and
+/// `http-proxy` all map to `http_proxy`. Then, each character that is not an
+/// ASCII letter, digit, or underscore is replaced with an underscore, and
+/// repeated, leading, and trailing underscores are removed, so `http.proxy`
+/// and `http--proxy` also map to `http_proxy`. The original key name must
+/// still be used when calling into gio.
+///
+/// Aborts if the result is empty, a keyword, or otherwise not a valid
+/// identifier.
+fn key_func_ident(key_name: &str) -> Ident {
+    let sanitized = key_name
+        .to_snake_case()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    let ident_name = sanitized
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+
+    if ident_name.is_empty() {
+        abort_call_site!("key name `{}` has no valid identifier characters", key_name);
+    }
+
+    syn::parse_str::<Ident>(&ident_name).unwrap_or_else(|_| {
+        abort_call_site!(
+            "key name `{}` maps to `{}`, which is not a valid identifier; consider skipping it with `#[gen_settings_skip( .. )]`",
+            key_name,
+            ident_name
+        )
+    })
+}
+
+/// Creates the identifier of a generated type, or of a variant or flag of one,
+/// erroring instead of panicking if the name converted from `origin` is not a
+/// valid identifier, for example, `2xScale` for the key `2x-scale`.
+fn generated_ident(name: &str, origin: std::fmt::Arguments<'_>) -> Ident {
+    syn::parse_str::<Ident>(name).unwrap_or_else(|_| {
+        abort_call_site!(
+            "{} maps to `{}`, which is not a valid identifier; consider using `#[gen_settings_define( .. )]` or skipping the key with `#[gen_settings_skip( .. )]`",
+            origin,
+            name
+        )
+    })
+}
+
 #[derive(Clone)]
 pub struct Context {
     arg_type: String,
     ret_type: String,
     auxiliary: Option<proc_macro2::TokenStream>,
+    to_variant: Option<proc_macro2::TokenStream>,
+    // Boxed, as it is rarely used and would otherwise make `GetResult` much larger
+    ret_conversions: Option<Box<RetConversions>>,
+}
+
+/// Conversions of a return type that doesn't implement
+/// [`FromVariant`](gio::glib::FromVariant) and [`ToVariant`](gio::glib::ToVariant)
+#[derive(Clone)]
+struct RetConversions {
+    from_variant: proc_macro2::TokenStream,
+    to_variant: proc_macro2::TokenStream,
 }
 
 impl Context {
@@ -319,6 +1843,8 @@ impl Context {
             arg_type: arg_type.to_string(),
             ret_type: ret_type.to_string(),
             auxiliary: None,
+            to_variant: None,
+            ret_conversions: None,
         }
     }
 
@@ -327,31 +1853,178 @@ impl Context {
             arg_type: type_.to_string(),
             ret_type: type_.to_string(),
             auxiliary: Some(auxiliary),
+            to_variant: None,
+            ret_conversions: None,
+        }
+    }
+
+    /// Use the given expression to convert the setter's `value` to a variant,
+    /// instead of [`ToVariant`](gio::glib::ToVariant)
+    pub fn with_to_variant(mut self, to_variant: proc_macro2::TokenStream) -> Self {
+        self.to_variant = Some(to_variant);
+        self
+    }
+
+    /// Use the given expression to convert `variant` to an `Option` of the
+    /// return type, and `value`, a reference to the return type, back to a
+    /// variant, for return types that don't implement
+    /// [`FromVariant`](gio::glib::FromVariant) and [`ToVariant`](gio::glib::ToVariant)
+    pub fn with_ret_conversions(
+        mut self,
+        from_variant: proc_macro2::TokenStream,
+        ret_to_variant: proc_macro2::TokenStream,
+    ) -> Self {
+        self.ret_conversions = Some(Box::new(RetConversions {
+            from_variant,
+            to_variant: ret_to_variant,
+        }));
+        self
+    }
+}
+
+/// Creates a setter that parses the value of the key from its nick, erroring
+/// on unknown nicks
+fn from_str_setter_items(key: &SchemaKey, enum_name: &str, crates: &Crates) -> Items {
+    let glib = &crates.glib;
+
+    let key_name = key.name.as_str();
+    let getter_func_ident = key_func_ident(key_name);
+    let try_setter_func_ident = format_ident!("try_set_{}", getter_func_ident);
+    let from_str_setter_func_ident = format_ident!("set_{}_from_str", getter_func_ident);
+    let enum_ident = format_ident!("{}", enum_name);
+
+    let docs = format!(
+        "Sets `{}` from the nick of the value, erroring if the nick is unknown or the key is not writable.",
+        key_name
+    );
+
+    Items::new(
+        [&from_str_setter_func_ident],
+        quote! {
+            #[doc = #docs]
+            pub fn #from_str_setter_func_ident(&self, nick: &str) -> std::result::Result<(), #glib::BoolError> {
+                let value = <#enum_ident as std::str::FromStr>::from_str(nick)?;
+                self.#try_setter_func_ident(value)
+            }
+        },
+    )
+}
+
+/// Creates a getter that returns the nick of the current value of the key
+/// without allocating, as the set of nicks is known at compile time
+fn nick_getter_items(key: &SchemaKey, nicks: &[&str], crates: &Crates, field: &Field) -> Items {
+    let Crates { gio, glib } = crates;
+    let settings = field.access();
+
+    let key_name = key.name.as_str();
+    let nick_func_ident = format_ident!("{}_nick", key_func_ident(key_name));
+
+    let docs = format!(
+        "Returns the nick of the current value of `{}` without allocating.",
+        key_name
+    );
+
+    Items::new(
+        [&nick_func_ident],
+        quote! {
+            #[doc = #docs]
+            pub fn #nick_func_ident(&self) -> &'static str {
+                let value = #gio::prelude::SettingsExt::value(&#settings, #key_name);
+                match #glib::Variant::str(&value) {
+                    #(Some(#nicks) => #nicks,)*
+                    _ => panic!("unknown value `{}` for key `{}`", value, #key_name),
+                }
+            }
+        },
+    )
+}
+
+/// Creates a getter that lazily yields the strings of a key of type `as`,
+/// instead of collecting them into a `Vec`
+fn string_array_iter_items(key: &SchemaKey, crates: &Crates, field: &Field) -> Items {
+    let Crates { gio, glib } = crates;
+    let settings = field.access();
+
+    let key_name = key.name.as_str();
+    let iter_func_ident = format_ident!("{}_iter", key_func_ident(key_name));
+
+    let docs = format!(
+        "Returns an iterator over the strings of `{}`, without collecting them into a `Vec`.",
+        key_name
+    );
+
+    Items::new(
+        [&iter_func_ident],
+        quote! {
+            #[doc = #docs]
+            pub fn #iter_func_ident(&self) -> impl Iterator<Item = String> {
+                let value = #gio::prelude::SettingsExt::value(&#settings, #key_name);
+                #glib::Variant::iter(&value).map(|item| {
+                    #glib::Variant::str(&item)
+                        .expect("expected an array of strings")
+                        .to_string()
+                })
+            }
+        },
+    )
+}
+
+/// Aborts if multiple nicks of the type map to the same identifier, for example,
+/// when the nicks only differ by case like `Auto` and `auto`
+fn ensure_unique_idents<'a>(type_name: &str, nicks: impl IntoIterator<Item = (&'a str, String)>) {
+    let mut seen = HashMap::new();
+
+    for (nick, ident) in nicks {
+        if let Some(other_nick) = seen.insert(ident.clone(), nick) {
+            abort_call_site!(
+                "nicks `{}` and `{}` of `{}` both map to `{}`; consider using `#[gen_settings_define( .. )]` or skipping the key with `#[gen_settings_skip( .. )]`",
+                other_nick,
+                nick,
+                type_name,
+                ident
+            );
         }
     }
 }
 
-/// Creates an enum with given name and (variant name, variant value) tuple. It implements
+/// Creates an enum for the key with given name and (variant name, variant value) tuple. It implements
 /// [`FromVariant`](gio::glib::FromVariant), [`ToVariant`](gio::glib::ToVariant),
-/// and [`StaticVariantType`](gio::glib::StaticVariantType).
+/// [`StaticVariantType`](gio::glib::StaticVariantType), and [`FromStr`] from the
+/// nicks, [`Default`] as the default value of the key, and has a `VARIANTS`
+/// constant containing all of the variants in order. The aliases of the key
+/// are also accepted when converting from a variant or string.
 ///
 /// The input names are converted to pascal case
 fn new_variant_enum(
     name: &str,
     variants: &[(&str, Option<i32>)],
+    key: &SchemaKey,
     visibility: syn::Visibility,
+    crates: &Crates,
 ) -> proc_macro2::TokenStream {
-    use heck::ToPascalCase;
-    use syn::spanned::Spanned;
+    let glib = &crates.glib;
 
+    let ident = generated_ident(name, format_args!("key `{}`", key.name));
     let variant_names = variants
         .iter()
         .map(|(variant_name, _)| variant_name)
         .collect::<Vec<_>>();
 
+    ensure_unique_idents(
+        name,
+        variant_names
+            .iter()
+            .map(|variant_name| (**variant_name, variant_name.to_pascal_case())),
+    );
+
     let variant_idents = variant_names
         .iter()
-        .map(|variant_name| Ident::new(&variant_name.to_pascal_case(), variant_name.span()))
+        .map(|variant_name| {
+            generated_ident(
+                &variant_name.to_pascal_case(),
+                format_args!("nick `{}` of `{}`", variant_name, name),
+            )
+        })
         .collect::<Vec<_>>();
 
     let variant_arms =
@@ -370,15 +2043,38 @@ fn new_variant_enum(
                 }
             });
 
-    let from_variant_arms =
-        variant_names
-            .iter()
-            .zip(variant_idents.iter())
-            .map(|(variant_name, variant_ident)| {
-                quote! {
-                    #variant_name => Some(Self::#variant_ident)
-                }
-            });
+    // Aliases are only accepted when converting to the enum, and are never
+    // produced when converting from it
+    let aliases = key
+        .aliases
+        .iter()
+        .flat_map(|aliases| &aliases.aliases)
+        .map(|alias| {
+            let index = variant_names
+                .iter()
+                .position(|variant_name| **variant_name == alias.target)
+                .unwrap_or_else(|| {
+                    abort_call_site!(
+                        "target `{}` of alias `{}` of key `{}` is not one of its nicks",
+                        alias.target,
+                        alias.value,
+                        key.name
+                    )
+                });
+            (alias.value.as_str(), &variant_idents[index])
+        })
+        .collect::<Vec<_>>();
+
+    let from_variant_arms = variant_names
+        .iter()
+        .map(|variant_name| **variant_name)
+        .zip(variant_idents.iter())
+        .chain(aliases.iter().copied())
+        .map(|(variant_name, variant_ident)| {
+            quote! {
+                #variant_name => Some(Self::#variant_ident)
+            }
+        });
 
     let to_variant_arms =
         variant_names
@@ -386,12 +2082,36 @@ fn new_variant_enum(
             .zip(variant_idents.iter())
             .map(|(variant_name, variant_ident)| {
                 quote! {
-                    Self::#variant_ident => gio::glib::ToVariant::to_variant(#variant_name)
+                    Self::#variant_ident => #glib::ToVariant::to_variant(#variant_name)
                 }
             });
 
-    let name_pascal_case = name.to_pascal_case();
-    let ident = Ident::new(&name_pascal_case, name_pascal_case.span());
+    let from_str_arms = variant_names
+        .iter()
+        .map(|variant_name| **variant_name)
+        .zip(variant_idents.iter())
+        .chain(aliases.iter().copied())
+        .map(|(variant_name, variant_ident)| {
+            quote! {
+                #variant_name => Ok(Self::#variant_ident)
+            }
+        });
+
+    let default_ident = key
+        .default_string()
+        .and_then(|default_nick| {
+            variant_names
+                .iter()
+                .position(|variant_name| **variant_name == default_nick.as_ref())
+        })
+        .map(|index| &variant_idents[index])
+        .unwrap_or_else(|| {
+            abort_call_site!(
+                "default value `{}` of key `{}` is not one of its nicks",
+                key.default_value(),
+                key.name
+            )
+        });
 
     quote! {
         #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -400,14 +2120,26 @@ fn new_variant_enum(
             #(#variant_arms),*
         }
 
-        impl gio::glib::StaticVariantType for #ident {
-            fn static_variant_type() -> std::borrow::Cow<'static, gio::glib::VariantTy> {
-                std::borrow::Cow::Borrowed(gio::glib::VariantTy::STRING)
+        impl std::default::Default for #ident {
+            /// The default value of the key, as specified in the schema.
+            fn default() -> Self {
+                Self::#default_ident
+            }
+        }
+
+        impl #ident {
+            /// All variants of the enum, in the order they are declared in the schema.
+            pub const VARIANTS: &'static [Self] = &[#(Self::#variant_idents),*];
+        }
+
+        impl #glib::StaticVariantType for #ident {
+            fn static_variant_type() -> std::borrow::Cow<'static, #glib::VariantTy> {
+                std::borrow::Cow::Borrowed(#glib::VariantTy::STRING)
             }
         }
 
-        impl gio::glib::FromVariant for #ident {
-            fn from_variant(variant: &gio::glib::Variant) -> Option<Self> {
+        impl #glib::FromVariant for #ident {
+            fn from_variant(variant: &#glib::Variant) -> Option<Self> {
                 match variant.get::<String>()?.as_str() {
                     #(#from_variant_arms),*,
                     _ => None,
@@ -415,17 +2147,28 @@ fn new_variant_enum(
             }
         }
 
-        impl gio::glib::ToVariant for #ident {
-            fn to_variant(&self) -> gio::glib::Variant {
+        impl #glib::ToVariant for #ident {
+            fn to_variant(&self) -> #glib::Variant {
                 match self {
                     #(#to_variant_arms),*
                 }
             }
         }
 
-        impl std::convert::From<#ident> for gio::glib::Variant {
-            fn from(this: #ident) -> gio::glib::Variant {
-                gio::glib::ToVariant::to_variant(&this)
+        impl std::str::FromStr for #ident {
+            type Err = #glib::BoolError;
+
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                match s {
+                    #(#from_str_arms),*,
+                    _ => Err(#glib::bool_error!("invalid nick `{}` for `{}`", s, #name)),
+                }
+            }
+        }
+
+        impl std::convert::From<#ident> for #glib::Variant {
+            fn from(this: #ident) -> #glib::Variant {
+                #glib::ToVariant::to_variant(&this)
             }
         }
     }
@@ -0,0 +1,147 @@
+use quote::ToTokens;
+
+use super::{Context, Crates};
+
+/// Creates a context from a type signature, if all of the types in it are known.
+///
+/// Container types are mapped recursively, so `(sa{sv})` would have
+/// `(&str, &HashMap<String, Variant>)` as parameter type and
+/// `(String, HashMap<String, Variant>)` as return type.
+pub fn context(signature: &str, crates: &Crates) -> Option<Context> {
+    let glib = crates.glib.to_token_stream().to_string();
+    match parse(signature, &glib)? {
+        (context, "") => Some(context),
+        _ => None,
+    }
+}
+
+/// The Rust type of a basic type signature, such as `i32` for `i`.
+pub fn basic_type(signature: &str, crates: &Crates) -> Option<syn::Type> {
+    let glib = crates.glib.to_token_stream().to_string();
+    let mut chars = signature.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(syn::parse_str(&basic(c, &glib)?.ret_type).unwrap()),
+        _ => None,
+    }
+}
+
+/// The Rust type of a basic type signature that can be held by a `glib::Value`,
+/// along with the wider type it is held as if it can't be held as is. There
+/// are no 16-bit values, so those are widened, and handles are only
+/// meaningful along with a file descriptor list, so they are not supported.
+pub fn value_types(signature: &str, crates: &Crates) -> Option<(syn::Type, Option<syn::Type>)> {
+    let stored_type = basic_type(signature, crates)?;
+    let widened_type = match signature {
+        "n" => Some(syn::parse_quote!(i32)),
+        "q" => Some(syn::parse_quote!(u32)),
+        "h" => return None,
+        _ => None,
+    };
+    Some((stored_type, widened_type))
+}
+
+/// Parses a single complete type at the start of the signature, returning
+/// its context and the rest of the signature.
+fn parse<'a>(signature: &'a str, glib: &str) -> Option<(Context, &'a str)> {
+    let mut chars = signature.chars();
+    let first = chars.next()?;
+    let rest = chars.as_str();
+
+    match first {
+        'a' => {
+            if let Some(rest) = rest.strip_prefix('{') {
+                let mut chars = rest.chars();
+                let key_char = chars.next()?;
+                // `f64` does not implement `Hash` and `Eq`
+                if key_char == 'd' {
+                    return None;
+                }
+                let key = basic(key_char, glib)?;
+                let (value, rest) = parse(chars.as_str(), glib)?;
+                let rest = rest.strip_prefix('}')?;
+
+                let map_type = format!(
+                    "std::collections::HashMap<{}, {}>",
+                    key.ret_type, value.ret_type
+                );
+                Some((
+                    Context::new_dissimilar(&format!("&{}", map_type), &map_type),
+                    rest,
+                ))
+            } else {
+                let (element, rest) = parse(rest, glib)?;
+                Some((
+                    Context::new_dissimilar(
+                        &format!("&[{}]", element.arg_type),
+                        &format!("Vec<{}>", element.ret_type),
+                    ),
+                    rest,
+                ))
+            }
+        }
+        'm' => {
+            let (inner, rest) = parse(rest, glib)?;
+            Some((
+                Context::new_dissimilar(
+                    &format!("Option<{}>", inner.arg_type),
+                    &format!("Option<{}>", inner.ret_type),
+                ),
+                rest,
+            ))
+        }
+        '(' => {
+            let mut fields = Vec::new();
+            let mut rest = rest;
+            while !rest.starts_with(')') {
+                let (field, field_rest) = parse(rest, glib)?;
+                fields.push(field);
+                rest = field_rest;
+            }
+            Some((
+                Context::new_dissimilar(
+                    &tuple_type(fields.iter().map(|field| field.arg_type.as_str())),
+                    &tuple_type(fields.iter().map(|field| field.ret_type.as_str())),
+                ),
+                &rest[1..],
+            ))
+        }
+        'v' => Some((
+            Context::new_dissimilar(
+                &format!("&{}::Variant", glib),
+                &format!("{}::Variant", glib),
+            ),
+            rest,
+        )),
+        c => Some((basic(c, glib)?, rest)),
+    }
+}
+
+/// Types of the basic type signatures, which are also the only ones
+/// allowed as dictionary keys
+fn basic(c: char, glib: &str) -> Option<Context> {
+    let context = match c {
+        'b' => Context::new("bool"),
+        'y' => Context::new("u8"),
+        'n' => Context::new("i16"),
+        'q' => Context::new("u16"),
+        'i' => Context::new("i32"),
+        'u' => Context::new("u32"),
+        'x' => Context::new("i64"),
+        't' => Context::new("u64"),
+        'd' => Context::new("f64"),
+        'h' => Context::new(&format!("{}::variant::Handle", glib)),
+        's' => Context::new_dissimilar("&str", "String"),
+        _ => return None,
+    };
+    Some(context)
+}
+
+fn tuple_type<'a>(fields: impl Iterator<Item = &'a str>) -> String {
+    let fields = fields.collect::<Vec<_>>();
+
+    if fields.len() == 1 {
+        format!("({},)", fields[0])
+    } else {
+        format!("({})", fields.join(", "))
+    }
+}
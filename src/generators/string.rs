@@ -1,24 +1,53 @@
-use heck::ToPascalCase;
+use quote::{quote, ToTokens};
 
-use super::{Context, KeyGenerator, SchemaKey};
+use super::{Context, Crates, Field, KeyGenerator, SchemaKey};
 
-pub fn key_generator(key: &SchemaKey, aux_visibility: syn::Visibility) -> KeyGenerator<'_> {
+pub fn key_generator<'a>(
+    key: &'a SchemaKey,
+    choice_enum_name: &str,
+    aux_visibility: syn::Visibility,
+    crates: &'a Crates,
+    field: &'a Field,
+    gstring_getters: bool,
+) -> KeyGenerator<'a> {
     if let Some(ref choices) = key.choices {
-        let choice_enum_name = key.name.to_pascal_case();
         let choice_enum_token_stream = super::new_variant_enum(
-            &choice_enum_name,
+            choice_enum_name,
             &choices
                 .choices
                 .iter()
                 .map(|choice| (choice.value.as_str(), None))
                 .collect::<Vec<_>>(),
+            key,
             aux_visibility,
+            crates,
         );
+        let nicks = choices
+            .choices
+            .iter()
+            .map(|choice| choice.value.as_str())
+            .collect::<Vec<_>>();
         KeyGenerator::new(
             key,
-            Context::new_with_aux(&choice_enum_name, choice_enum_token_stream),
+            Context::new_with_aux(choice_enum_name, choice_enum_token_stream),
+            crates,
+            field,
         )
+        .with_extra_methods(super::nick_getter_items(key, &nicks, crates, field))
+    } else if gstring_getters {
+        let glib = &crates.glib;
+        let ret_type = format!("{}::GString", glib.to_token_stream());
+        let context = Context::new_dissimilar("&str", &ret_type).with_ret_conversions(
+            quote! { #glib::Variant::str(variant).map(#glib::GString::from) },
+            quote! { #glib::ToVariant::to_variant(value.as_str()) },
+        );
+        KeyGenerator::new(key, context, crates, field)
     } else {
-        KeyGenerator::new(key, Context::new_dissimilar("&str", "String"))
+        KeyGenerator::new(
+            key,
+            Context::new_dissimilar("&str", "String"),
+            crates,
+            field,
+        )
     }
 }
@@ -0,0 +1,267 @@
+use quote::quote;
+
+use crate::generators::{Crates, StructInfo};
+
+/// The methods of the struct that are generated regardless of the options,
+/// along with `from_settings`, which has the visibility of the constructors
+pub fn common_methods<'k>(
+    info: &StructInfo<'_>,
+    constructor_vis: &syn::Visibility,
+    key_names: impl Iterator<Item = &'k str>,
+) -> proc_macro2::TokenStream {
+    let StructInfo {
+        field,
+        crates: Crates { gio, glib },
+        ..
+    } = info;
+    let settings = field.access();
+    let construct_self = field.construct(quote! { #gio::Settings::clone(settings) });
+    let construct_from_settings = field.construct(quote! { settings });
+
+    quote! {
+        /// Creates the settings from an existing `gio::Settings`, which is
+        /// expected to use the same schema.
+        #constructor_vis fn from_settings(settings: #gio::Settings) -> Self {
+            #construct_from_settings
+        }
+
+        /// Names of all the keys in the schema, in the order they are defined.
+        pub const KEYS: &'static [&'static str] = &[#(#key_names),*];
+
+        /// Resets all the keys in the schema to their default values.
+        ///
+        /// In delay-apply mode, the resets are pending until `apply` is called,
+        /// so they can be wrapped in a transaction with the other changes.
+        pub fn reset_all(&self) {
+            for key in Self::KEYS {
+                #gio::prelude::SettingsExt::reset(&#settings, key);
+            }
+        }
+
+        /// Returns the path where the settings are stored.
+        ///
+        /// Unlike `gio::Settings::path`, this is not optional, as the path is
+        /// always known once the settings are constructed.
+        pub fn schema_path(&self) -> #glib::GString {
+            #gio::prelude::SettingsExt::path(&#settings)
+                .expect("constructed settings must have a path")
+        }
+
+        /// Lists the names of the children declared with `<child>` in the schema,
+        /// which can be accessed with `gio::Settings::child`.
+        pub fn children(&self) -> Vec<String> {
+            #gio::prelude::SettingsExt::list_children(&#settings)
+                .into_iter()
+                .map(std::string::String::from)
+                .collect()
+        }
+
+        /// Iterates over all the keys in the schema, yielding the key name and its current value.
+        pub fn iter_keys(&self) -> impl Iterator<Item = (&'static str, #glib::Variant)> + '_ {
+            Self::KEYS
+                .iter()
+                .map(move |key| (*key, #gio::prelude::SettingsExt::value(&#settings, key)))
+        }
+
+        /// Returns a human-readable listing of all the keys in the schema, with
+        /// their current value, whether it is set by the user or the default,
+        /// and whether the key is writable, for example, to attach to bug reports.
+        pub fn dump(&self) -> String {
+            use std::fmt::Write;
+
+            let mut dump = String::new();
+            writeln!(
+                dump,
+                "{} ({})",
+                #glib::prelude::ObjectExt::property::<String>(&#settings, "schema-id"),
+                self.schema_path()
+            )
+            .unwrap();
+            for key in Self::KEYS {
+                let value = #gio::prelude::SettingsExt::value(&#settings, key);
+                let origin = if #gio::prelude::SettingsExt::user_value(&#settings, key).is_some() {
+                    "user"
+                } else {
+                    "default"
+                };
+                let writability = if #gio::prelude::SettingsExt::is_writable(&#settings, key) {
+                    ""
+                } else {
+                    ", readonly"
+                };
+                writeln!(dump, "{}: {} ({}{})", key, value, origin, writability).unwrap();
+            }
+            dump
+        }
+
+        /// Connects to the `changed` signal of all keys, passing the name of the changed key.
+        pub fn connect_any_changed(&self, f: impl Fn(&Self, &str) + 'static) -> #glib::SignalHandlerId {
+            #gio::prelude::SettingsExt::connect_changed(&#settings, None, move |settings, key| {
+                f(&#construct_self, key)
+            })
+        }
+
+        /// Connects to when the pending changes in delay-apply mode are applied, passing
+        /// the names of the keys that were changed.
+        ///
+        /// Unlike `connect_any_changed`, this is only called once per `apply`, and it is
+        /// not called when the pending changes are reverted.
+        ///
+        /// This returns the ids of the two connected handlers, which both have to be
+        /// disconnected to remove the callback.
+        pub fn connect_applied(&self, f: impl Fn(&Self, &[String]) + 'static) -> [#glib::SignalHandlerId; 2] {
+            let pending_keys = std::rc::Rc::new(std::cell::RefCell::new(Vec::<String>::new()));
+
+            let pending_keys_clone = std::rc::Rc::clone(&pending_keys);
+            let changed_handler_id = #gio::prelude::SettingsExt::connect_changed(&#settings, None, move |settings, key| {
+                let mut pending_keys = pending_keys_clone.borrow_mut();
+
+                if !#gio::prelude::SettingsExt::has_unapplied(settings) {
+                    // The pending changes are reverted or the key is changed outside
+                    // of delay-apply mode.
+                    pending_keys.clear();
+                } else if !pending_keys.iter().any(|pending_key| pending_key == key) {
+                    pending_keys.push(key.to_string());
+                }
+            });
+
+            let has_unapplied_handler_id = #gio::prelude::SettingsExt::connect_has_unapplied_notify(&#settings, move |settings| {
+                if #gio::prelude::SettingsExt::has_unapplied(settings) {
+                    return;
+                }
+
+                let applied_keys = pending_keys.take();
+                if !applied_keys.is_empty() {
+                    f(&#construct_self, &applied_keys);
+                }
+            });
+
+            [changed_handler_id, has_unapplied_handler_id]
+        }
+    }
+}
+
+/// The implementations of `Deref`, `DerefMut`, and `Debug`, which delegate to
+/// the settings
+pub fn settings_impls(info: &StructInfo<'_>) -> proc_macro2::TokenStream {
+    let StructInfo {
+        ident: struct_ident,
+        field,
+        crates: Crates { gio, .. },
+        ..
+    } = info;
+    let settings = field.access();
+    let settings_mut = field.access_mut();
+
+    quote! {
+        impl std::ops::Deref for #struct_ident {
+            type Target = #gio::Settings;
+
+            fn deref(&self) -> &Self::Target {
+                &#settings
+            }
+        }
+
+        impl std::ops::DerefMut for #struct_ident {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                #settings_mut
+            }
+        }
+
+        impl std::fmt::Debug for #struct_ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                std::fmt::Debug::fmt(&#settings, f)
+            }
+        }
+    }
+}
+
+/// The implementations of `PartialEq`, `Eq`, and `Hash`, which compare the
+/// schema id and path instead of the settings objects
+pub fn eq_by_schema_impls(info: &StructInfo<'_>) -> proc_macro2::TokenStream {
+    let StructInfo {
+        ident: struct_ident,
+        field,
+        crates: Crates { gio, .. },
+        ..
+    } = info;
+    let settings = field.access();
+    let other_settings = field.access_of(quote! { other });
+
+    quote! {
+        impl std::cmp::PartialEq for #struct_ident {
+            fn eq(&self, other: &Self) -> bool {
+                #gio::prelude::SettingsExt::schema_id(&#settings)
+                    == #gio::prelude::SettingsExt::schema_id(&#other_settings)
+                    && #gio::prelude::SettingsExt::path(&#settings)
+                        == #gio::prelude::SettingsExt::path(&#other_settings)
+            }
+        }
+
+        impl std::cmp::Eq for #struct_ident {}
+
+        impl std::hash::Hash for #struct_ident {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                std::hash::Hash::hash(&#gio::prelude::SettingsExt::schema_id(&#settings), state);
+                std::hash::Hash::hash(&#gio::prelude::SettingsExt::path(&#settings), state);
+            }
+        }
+    }
+}
+
+/// The conversions between the struct and `gio::Settings`, which
+/// `ValueDelegate` needs if it can't delegate to the field directly.
+/// `From<gio::Settings>` is only implemented if `is_constructor_public`, as it
+/// can't be restricted.
+pub fn from_impls(info: &StructInfo<'_>, is_constructor_public: bool) -> proc_macro2::TokenStream {
+    let StructInfo {
+        ident: struct_ident,
+        field,
+        crates: Crates { gio, .. },
+        ..
+    } = info;
+
+    let mut token_stream = proc_macro2::TokenStream::new();
+    if is_constructor_public {
+        let construct_from = field.construct(quote! { settings });
+        token_stream.extend(quote! {
+            impl std::convert::From<#gio::Settings> for #struct_ident {
+                fn from(settings: #gio::Settings) -> Self {
+                    #construct_from
+                }
+            }
+        });
+    }
+
+    token_stream.extend(quote! {
+        impl std::convert::From<#struct_ident> for #gio::Settings {
+            fn from(this: #struct_ident) -> Self {
+                #gio::Settings::clone(&this)
+            }
+        }
+
+        impl<'a> std::convert::From<&'a #struct_ident> for #gio::Settings {
+            fn from(this: &'a #struct_ident) -> Self {
+                #gio::Settings::clone(this)
+            }
+        }
+    });
+    token_stream
+}
+
+/// A constant that fails to compile if the struct is `Send`
+pub fn assert_not_send(struct_ident: &syn::Ident) -> proc_macro2::TokenStream {
+    // The path to `some_item` is ambiguous only if the second impl
+    // also applies, that is, if the struct is `Send`.
+    quote! {
+        const _: fn() = || {
+            trait AmbiguousIfSend<A> {
+                fn some_item() {}
+            }
+            impl<T: ?Sized> AmbiguousIfSend<()> for T {}
+            struct Invalid;
+            impl<T: ?Sized + Send> AmbiguousIfSend<Invalid> for T {}
+            let _ = <#struct_ident as AmbiguousIfSend<_>>::some_item;
+        };
+    }
+}
@@ -2,11 +2,22 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![doc = include_str!("../README.md")]
 
+mod auxiliary;
+mod constructor;
+mod describe;
+mod functions;
 mod generators;
+mod impls;
+mod object;
+mod overrides;
 mod schema;
+mod traits;
+mod values;
 
 use deluxe::SpannedValue;
-use proc_macro_error::{abort, emit_call_site_error, emit_error, emit_warning, proc_macro_error};
+use proc_macro_error::{
+    abort, abort_call_site, emit_call_site_error, emit_error, proc_macro_error,
+};
 use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
@@ -14,11 +25,23 @@ use syn::{
     Token,
 };
 
-use std::{collections::HashMap, fs::File, io::BufReader};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
 
 use crate::{
-    generators::{GetResult, KeyGenerators, OverrideType},
-    schema::{KeySignature as SchemaKeySignature, SchemaList},
+    auxiliary::AuxiliaryTypes,
+    functions::FunctionsModule,
+    generators::{
+        check_method_names, Crates, Field, GetResult, KeyGenerators, StructInfo,
+        RESERVED_METHOD_NAMES,
+    },
+    overrides::{is_helper_attr, parse_overrides},
+    schema::{Schema, SchemaList},
+    traits::{AccessorTrait, SettingsExtTrait},
+    values::ValuesStruct,
 };
 
 // TODO:
@@ -27,35 +50,112 @@ use crate::{
 // * Improve enum generation (create enum based on its definition, instead of by key; also add doc alias for its id)
 // * Add way to map setter and getters value
 // * Add `bind_#key writable`, `user_#key_value`, `connect_#key_writable_changed` variants
-// * Add trybuild tests
-// * Support for multiple schema
 
-#[derive(deluxe::ParseMetaItem)]
+#[derive(Clone, deluxe::ParseMetaItem)]
 struct GenSettings {
-    file: SpannedValue<String>,
+    file: Option<SchemaFiles>,
+    dir: Option<SpannedValue<String>>,
+    xml: Option<SpannedValue<String>>,
     id: Option<SpannedValue<String>>,
+    ids: Option<SpannedValue<Vec<SpannedValue<String>>>>,
+    names: Option<SpannedValue<Vec<SpannedValue<String>>>>,
+    gio_crate: Option<SpannedValue<String>>,
+    glib_crate: Option<SpannedValue<String>>,
+    path: Option<SpannedValue<String>>,
+    path_template: Option<SpannedValue<String>>,
+    field_name: Option<SpannedValue<String>>,
+    eq_by_schema: Option<bool>,
+    assert_not_send: Option<bool>,
+    accessor_trait: Option<bool>,
+    settings_ext: Option<SpannedValue<bool>>,
+    no_constructor: Option<bool>,
+    constructor_visibility: Option<SpannedValue<String>>,
+    functions_module: Option<SpannedValue<String>>,
+    values_struct: Option<bool>,
+    describe: Option<bool>,
+    only: Option<SpannedValue<Vec<SpannedValue<String>>>>,
+    deny_unknown_keys: Option<bool>,
+    lazy: Option<SpannedValue<bool>>,
+    object: Option<bool>,
+    #[deluxe(flatten)]
+    key_options: KeyOptions,
 }
 
-#[derive(deluxe::ParseAttributes)]
-struct GenSettingsDefine {
-    signature: Option<SpannedValue<String>>,
-    key_name: Option<SpannedValue<String>>,
-    arg_type: SpannedValue<String>,
-    ret_type: SpannedValue<String>,
+/// The options that apply to the methods generated for each key
+#[derive(Clone, deluxe::ParseMetaItem)]
+struct KeyOptions {
+    gtk_crate: Option<SpannedValue<String>>,
+    string_array_into_iter: Option<bool>,
+    string_array_iter: Option<bool>,
+    gstring_getters: Option<bool>,
+    must_use_getters: Option<bool>,
+    connect_changed_with_old_value: Option<bool>,
+    connect_changed_debounced: Option<bool>,
+    try_getters: Option<bool>,
 }
 
-#[derive(deluxe::ParseAttributes)]
-struct GenSettingsSkip {
-    signature: Option<SpannedValue<String>>,
-    key_name: Option<SpannedValue<String>>,
+impl KeyOptions {
+    fn enable(self, key_generators: &mut KeyGenerators<'_>) {
+        if self.string_array_into_iter.unwrap_or(false) {
+            key_generators.enable_string_array_into_iter();
+        }
+        if self.string_array_iter.unwrap_or(false) {
+            key_generators.enable_string_array_iter();
+        }
+        if self.gstring_getters.unwrap_or(false) {
+            key_generators.enable_gstring_getters();
+        }
+        if self.must_use_getters.unwrap_or(false) {
+            key_generators.enable_must_use_getters();
+        }
+        if self.connect_changed_with_old_value.unwrap_or(false) {
+            key_generators.enable_connect_changed_with_old_value();
+        }
+        if self.connect_changed_debounced.unwrap_or(false) {
+            key_generators.enable_connect_changed_debounced();
+        }
+        if self.try_getters.unwrap_or(false) {
+            key_generators.enable_try_getters();
+        }
+        if let Some(gtk_crate) = self.gtk_crate {
+            key_generators.enable_gtk_expressions(parse_crate_path(gtk_crate));
+        }
+    }
 }
 
+/// One or more schema files, specified as either a string or an array of strings
+#[derive(Clone)]
+struct SchemaFiles(Vec<SpannedValue<String>>);
+
+impl deluxe::ParseMetaItem for SchemaFiles {
+    fn parse_meta_item(input: ParseStream<'_>, mode: deluxe::ParseMode) -> deluxe::Result<Self> {
+        if input.peek(syn::token::Bracket) {
+            let files = Vec::<SpannedValue<String>>::parse_meta_item(input, mode)?;
+
+            if files.is_empty() {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "expected at least one schema file",
+                ));
+            }
+
+            Ok(Self(files))
+        } else {
+            SpannedValue::<String>::parse_meta_item(input, mode).map(|file| Self(vec![file]))
+        }
+    }
+}
+
+#[derive(Clone)]
 struct SettingsStruct {
     attrs: Vec<syn::Attribute>,
     vis: syn::Visibility,
     struct_token: Token![struct],
     ident: syn::Ident,
     semi_token: Token![;],
+    gio_crate: syn::Path,
+    field_name: Option<syn::Ident>,
+    is_lazy: bool,
 }
 
 impl Parse for SettingsStruct {
@@ -66,20 +166,322 @@ impl Parse for SettingsStruct {
             struct_token: input.parse()?,
             ident: input.parse()?,
             semi_token: input.parse()?,
+            gio_crate: syn::parse_quote!(gio),
+            field_name: None,
+            is_lazy: false,
         })
     }
 }
 
 impl ToTokens for SettingsStruct {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        for attr in &self.attrs {
+            if !is_helper_attr(attr) {
+                attr.to_tokens(tokens);
+            }
+        }
         self.vis.to_tokens(tokens);
         self.struct_token.to_tokens(tokens);
         self.ident.to_tokens(tokens);
 
-        let field: syn::FieldsUnnamed = syn::parse_quote!((gio::Settings));
-        field.to_tokens(tokens);
+        let gio = &self.gio_crate;
+        let field_type: syn::Type = if self.is_lazy {
+            syn::parse_quote!(std::cell::OnceCell<#gio::Settings>)
+        } else {
+            syn::parse_quote!(#gio::Settings)
+        };
+        if let Some(ref field_name) = self.field_name {
+            let fields: syn::FieldsNamed = syn::parse_quote!({ #field_name: #field_type });
+            fields.to_tokens(tokens);
+        } else {
+            let fields: syn::FieldsUnnamed = syn::parse_quote!((#field_type));
+            fields.to_tokens(tokens);
+
+            self.semi_token.to_tokens(tokens);
+        }
+    }
+}
+
+fn is_valid_path(path: &str) -> bool {
+    path.starts_with('/') && path.ends_with('/') && !path.contains("//")
+}
+
+fn parse_schema_file(path: &Path, span: proc_macro2::Span) -> SchemaList {
+    let schema_bytes = fs::read(path).unwrap_or_else(|err| {
+        abort!(
+            span,
+            "failed to open schema file `{}`: {}",
+            path.display(),
+            err
+        );
+    });
+    SchemaList::from_bytes(&schema_bytes).unwrap_or_else(|err| {
+        abort!(
+            span,
+            "failed to parse schema file `{}`: {}",
+            path.display(),
+            err
+        )
+    })
+}
+
+/// Parses the schema list from either `file`, `dir`, or `xml`, or from the
+/// installed schemas if only `id` is specified, along with the span to report
+/// errors about the schema at
+fn parse_schema_list(
+    file_attr: Option<SchemaFiles>,
+    dir_attr: Option<SpannedValue<String>>,
+    xml_attr: Option<SpannedValue<String>>,
+    id_attr: Option<&SpannedValue<String>>,
+) -> (proc_macro2::Span, SchemaList) {
+    match (file_attr, dir_attr, xml_attr) {
+        (Some(SchemaFiles(file_attrs)), None, None) => {
+            let mut file_attrs = file_attrs.into_iter();
+
+            let first_file_attr = file_attrs.next().unwrap();
+            let first_file_attr_span = first_file_attr.span();
+            let mut schema_list = parse_schema_file(
+                Path::new(&SpannedValue::into_inner(first_file_attr)),
+                first_file_attr_span,
+            );
+
+            for file_attr in file_attrs {
+                let file_attr_span = file_attr.span();
+                let other = parse_schema_file(
+                    Path::new(&SpannedValue::into_inner(file_attr)),
+                    file_attr_span,
+                );
+
+                if let Err(err) = schema_list.merge(other) {
+                    emit_error!(file_attr_span, "failed to merge schema file: {}", err);
+                }
+            }
+
+            (first_file_attr_span, schema_list)
+        }
+        (None, Some(dir_attr), None) => {
+            let dir_attr_span = dir_attr.span();
+            let schema_dir = SpannedValue::into_inner(dir_attr);
+
+            let schema_id = match id_attr {
+                Some(id_attr) => id_attr.as_str(),
+                None => abort!(dir_attr_span, "`id` must be specified when using `dir`"),
+            };
+
+            let schema_file_paths = schema::schema_files_in_dir(Path::new(&schema_dir))
+                .unwrap_or_else(|err| {
+                    abort!(dir_attr_span, "failed to read schema directory: {}", err);
+                });
+            let mut schema_list = schema_file_paths
+                .iter()
+                .map(|path| parse_schema_file(path, dir_attr_span))
+                .find(|schema_list| {
+                    schema_list
+                        .schemas
+                        .iter()
+                        .any(|schema| schema.id == schema_id)
+                })
+                .unwrap_or_else(|| {
+                    abort!(
+                        dir_attr_span,
+                        "no schema with id `{}` found in the directory",
+                        schema_id
+                    );
+                });
+            schema_list.schemas.retain(|schema| schema.id == schema_id);
+
+            (dir_attr_span, schema_list)
+        }
+        (None, None, Some(xml_attr)) => {
+            let xml_attr_span = xml_attr.span();
+            let schema_list = SchemaList::from_xml(&SpannedValue::into_inner(xml_attr))
+                .unwrap_or_else(|err| abort!(xml_attr_span, "failed to parse schema: {}", err));
+
+            (xml_attr_span, schema_list)
+        }
+        (None, None, None) => {
+            let Some(id_attr) = id_attr else {
+                abort_call_site!("must specify either `file`, `dir`, `xml`, or `id`");
+            };
+            let id_attr_span = id_attr.span();
+            let schema_id = id_attr.as_str();
+
+            // Directories that don't exist and files that fail to be read or parsed,
+            // whether schema or enum files, are skipped, as they are not under the
+            // user's control
+            let parse_installed_file = |path: &PathBuf| {
+                let schema_bytes = fs::read(path).ok()?;
+                SchemaList::from_bytes(&schema_bytes).ok()
+            };
+
+            let (schema_dir, mut schema_list) = schema::installed_schema_dirs()
+                .into_iter()
+                .filter_map(|schema_dir| {
+                    let schema_file_paths = schema::schema_files_in_dir(&schema_dir).ok()?;
+                    let schema_list = schema_file_paths
+                        .iter()
+                        .filter_map(parse_installed_file)
+                        .find(|schema_list| {
+                            schema_list
+                                .schemas
+                                .iter()
+                                .any(|schema| schema.id == schema_id)
+                        })?;
+                    Some((schema_dir, schema_list))
+                })
+                .next()
+                .unwrap_or_else(|| {
+                    abort!(
+                        id_attr_span,
+                        "no schema with id `{}` found in the installed schemas; specify it with `file`, `dir`, or `xml` instead",
+                        schema_id
+                    );
+                });
+            schema_list.schemas.retain(|schema| schema.id == schema_id);
+
+            // Installed enums usually live in a separate file generated by `glib-mkenums`
+            for mut other in schema::enum_files_in_dir(&schema_dir)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(parse_installed_file)
+            {
+                other.schemas.clear();
+                other
+                    .enums
+                    .retain(|enum_| schema_list.enums.iter().all(|e| e.id != enum_.id));
+                other
+                    .flags
+                    .retain(|flag| schema_list.flags.iter().all(|f| f.id != flag.id));
+                if let Err(err) = schema_list.merge(other) {
+                    emit_error!(id_attr_span, "failed to merge enum file: {}", err);
+                }
+            }
+
+            (id_attr_span, schema_list)
+        }
+        _ => abort_call_site!("can only specify one of `file`, `dir`, and `xml`"),
+    }
+}
+
+fn parse_crate_path(attr: SpannedValue<String>) -> syn::Path {
+    let attr_span = attr.span();
+    syn::parse_str(&SpannedValue::into_inner(attr)).unwrap_or_else(|err| {
+        abort!(attr_span, "invalid crate path: {}", err);
+    })
+}
+
+/// Drops the keys that are not in `only_attr`, before anything is generated for
+/// them, so it is as if they were not in the schema
+fn retain_only_keys(schema: &mut Schema, only_attr: SpannedValue<Vec<SpannedValue<String>>>) {
+    let only_attr_span = only_attr.span();
+    let only_key_names = SpannedValue::into_inner(only_attr);
+
+    if only_key_names.is_empty() {
+        emit_error!(only_attr_span, "expected at least one key name");
+    }
+
+    let mut seen_key_names = HashSet::new();
+    for key_name in &only_key_names {
+        if !schema.keys.iter().any(|key| key.name == key_name.as_str()) {
+            emit_error!(key_name.span(), "key_name not found in the schema");
+        }
 
-        self.semi_token.to_tokens(tokens);
+        if !seen_key_names.insert(key_name.as_str()) {
+            emit_error!(key_name.span(), "duplicate key name");
+        }
+    }
+
+    schema
+        .keys
+        .retain(|key| seen_key_names.contains(key.name.as_str()));
+}
+
+fn parse_schema_id(id_attr: SpannedValue<String>, schema: &Schema) -> String {
+    let id_attr_span = id_attr.span();
+    let schema_id = SpannedValue::into_inner(id_attr);
+
+    if schema.id != schema_id {
+        emit_error!(
+            id_attr_span,
+            "id does not match the one specified in the schema file"
+        );
+    }
+
+    schema_id
+}
+
+fn parse_schema_path(path_attr: SpannedValue<String>, schema: &Schema) -> String {
+    let path_attr_span = path_attr.span();
+    let schema_path = SpannedValue::into_inner(path_attr);
+
+    if !is_valid_path(&schema_path) {
+        emit_error!(
+            path_attr_span,
+            "path must start and end with a slash and must not contain two adjacent slashes"
+        );
+    }
+
+    if let Some(ref fixed_path) = schema.path {
+        if *fixed_path != schema_path {
+            emit_error!(
+                path_attr_span,
+                "schema has a fixed path `{}`, and GLib aborts when it is constructed at a different one; remove the `path` attribute from the schema to make it relocatable",
+                fixed_path
+            );
+        }
+    }
+
+    schema_path
+}
+
+fn parse_schema_path_template(path_template_attr: SpannedValue<String>, schema: &Schema) -> String {
+    let path_template_attr_span = path_template_attr.span();
+    let schema_path_template = SpannedValue::into_inner(path_template_attr);
+
+    if schema_path_template.matches("{}").count() != 1 {
+        emit_error!(
+            path_template_attr_span,
+            "path template must contain exactly one `{{}}`"
+        );
+    } else if !is_valid_path(&schema_path_template.replace("{}", "profile")) {
+        emit_error!(
+            path_template_attr_span,
+            "path template must start and end with a slash and must not contain two adjacent slashes"
+        );
+    }
+
+    if let Some(ref fixed_path) = schema.path {
+        emit_error!(
+            path_template_attr_span,
+            "schema has a fixed path `{}`; only relocatable schemas can have a path template",
+            fixed_path
+        );
+    }
+
+    schema_path_template
+}
+
+/// Emits an error for each range with a type, since glib-compile-schemas
+/// ignores the whole file if a range has one, so the generated code would
+/// refer to a schema that can't be installed
+fn check_range_types(schema: &Schema, schema_source_span: proc_macro2::Span) {
+    for key in &schema.keys {
+        let Some(range) = &key.range else {
+            continue;
+        };
+        if let Some(range_type) = range.type_.as_deref() {
+            let suggestion = match (range_type, range.id.as_deref()) {
+                ("enum" | "flags", Some(id)) => format!("`{}=\"{}\"`", range_type, id),
+                _ => "the `enum` or `flags` attribute".to_string(),
+            };
+            emit_error!(
+                schema_source_span,
+                "range of key `{}` has type `{}`, which is not supported by GSettings; use {} on the key instead",
+                key.name,
+                range_type,
+                suggestion
+            );
+        }
     }
 }
 
@@ -88,10 +490,14 @@ impl ToTokens for SettingsStruct {
 /// The macro's main purpose is to reduce the risk of mistyping a key,
 /// using the wrong method to access values, inputting incorrect values,
 /// and to reduce boilerplate. Additionally, the summary, description,
-/// and default value are included in the documentation of each generated
-/// method. This would be beneficial if you use tools like
+/// wrapped key name and type, and default value are included in the
+/// documentation of each generated method. This would be beneficial if
+/// you use tools like
 /// [`rust-analyzer`](https://rust-analyzer.github.io/).
 ///
+/// The features summarized below are described in detail, with more examples,
+/// in the [crate documentation](crate).
+///
 /// **⚠️ IMPORTANT ⚠️**
 ///
 /// Both `gio` and `glib` need to be in scope, so unless they are direct crate
@@ -102,6 +508,10 @@ impl ToTokens for SettingsStruct {
 /// use gtk::{gio, glib};
 /// ```
 ///
+/// Alternatively, the paths to the crates can be specified with the
+/// `gio_crate` and `glib_crate` attributes, though `glib` is still required by
+/// the derived [`ValueDelegate`].
+///
 /// ### Example
 ///
 /// ```ignore
@@ -129,7 +539,9 @@ impl ToTokens for SettingsStruct {
 /// ```
 ///
 /// Note: The file path is relative to the project root or where the
-/// `Cargo.toml` file is located.
+/// `Cargo.toml` file is located. Instead of `file`, which can also be an array
+/// of files, the schema can be read from a directory with `dir`, specified
+/// inline with `xml`, or looked up in the installed schemas by `id` alone.
 ///
 /// ### Generated methods
 ///
@@ -137,14 +549,19 @@ impl ToTokens for SettingsStruct {
 /// for each key in the schema:
 ///
 /// * `set` -> `set_${key}`, which panics when writing in a readonly
-/// key, and `try_set_${key}`, which behaves the same as the original method.
+///   key, and `try_set_${key}`, which behaves the same as the original method.
 /// * `get` -> `${key}`
-/// * `connect_changed` -> `connect_${key}_changed`
+/// * `connect_changed` -> `connect_${key}_changed`, whose callback also gets
+///   the new value for keys with flags.
 /// * `bind` -> `bind_${key}`
 /// * `create_action` -> `create_${key}_action`
 /// * `default_value` -> `${key}_default_value`
 /// * `reset` -> `reset_${key}`
 ///
+/// Other methods are generated along with these, such as `${key}_is_default`
+/// for each key and `reset_all` for all keys. It fails to compile if a key
+/// generates a method that another key or the struct already has.
+///
 /// ### Known D-Bus type signatures
 ///
 /// The setter and getter methods has the following parameter and
@@ -153,15 +570,29 @@ impl ToTokens for SettingsStruct {
 /// | Type Signature | Parameter Type | Return Type   |
 /// | -------------- | -------------- | ------------- |
 /// | b              | `bool`         | `bool`        |
+/// | y              | `u8`           | `u8`          |
+/// | n              | `i16`          | `i16`         |
+/// | q              | `u16`          | `u16`         |
 /// | i              | `i32`          | `i32`         |
 /// | u              | `u32`          | `u32`         |
 /// | x              | `i64`          | `i64`         |
 /// | t              | `u64`          | `u64`         |
 /// | d              | `f64`          | `f64`         |
-/// | (ii)           | `(i32, i32`)   | `(i32, i32`)  |
-/// | as             | `&[&str]`      | `Vec<String>` |
+/// | h              | [`Handle`]     | [`Handle`]    |
+/// | v              | `&Variant`     | [`Variant`]   |
 /// | s *            | `&str`         | `String`      |
 ///
+/// Container types are also known, as long as all the types they contain
+/// are known. These are mapped recursively, where `T` is the parameter or
+/// return type of the contained type.
+///
+/// | Type Signature | Parameter Type   | Return Type      |
+/// | -------------- | ---------------- | ---------------- |
+/// | aT             | `&[T]`           | `Vec<T>`         |
+/// | a{KT}          | `&HashMap<K, T>` | `HashMap<K, T>`  |
+/// | mT             | `Option<T>`      | `Option<T>`      |
+/// | (T..)          | `(T, ..)`        | `(T, ..)`        |
+///
 /// \* If the key of type signature `s` has no `choice` attribute
 /// specified in the GSchema, the parameter and return types stated
 /// in the table would be applied. Otherwise, it will generate an
@@ -188,7 +619,8 @@ impl ToTokens for SettingsStruct {
 /// a const flag.
 ///
 /// The generated types, enum or bitflags, would have the same
-/// visibility and scope with the generated struct.
+/// visibility and scope with the generated struct, and are named after
+/// their key converted to pascal case.
 ///
 /// ### Skipping methods generation
 ///
@@ -215,6 +647,9 @@ impl ToTokens for SettingsStruct {
 /// }
 /// ```
 ///
+/// Whole categories of container types can also be skipped with `kind`, which
+/// is one of `array`, `dict`, and `tuple`.
+///
 /// ### Defining custom types
 ///
 /// ```ignore
@@ -245,8 +680,12 @@ impl ToTokens for SettingsStruct {
 /// The type specified in `arg_type` and `ret_type` has to be on scope or
 /// you can specify the full path.
 ///
-/// If you somehow do not want an enum parameter and return types for `s`
-/// type signature with choices. You can also use this to override that behavior.
+/// Defines by `key_name` take precedence over defines by `signature`, which
+/// are not applied on keys with choices unless `force = true` is specified.
+/// For arrays, `element_arg_type` and `element_ret_type` define only the
+/// element types instead. A define by `key_name` can also rename the generated
+/// enum or flags with `enum_name`, or hide the methods from the docs with
+/// `doc_hidden = true`.
 ///
 /// Note: The type has to implement both [`ToVariant`] and [`FromVariant`] or it
 /// would fail to compile.
@@ -275,9 +714,39 @@ impl ToTokens for SettingsStruct {
 /// let another_instance = ApplicationSettings::default();
 /// ```
 ///
+/// Along with `new`, `try_new`, `builder`, and `from_settings` are generated.
+///
+/// ### Other attributes
+///
+/// * `ids` and `names`: generate a struct for each of several schemas in a file.
+/// * `only`: the keys to generate, ignoring the others.
+/// * `deny_unknown_keys`: only allow skipping keys by `key_name`.
+/// * `path` and `path_template`: the path of a relocatable schema, or a
+///   template for `for_profile`.
+/// * `no_constructor` and `constructor_visibility`: skip or restrict the
+///   constructors.
+/// * `field_name` and `lazy`: a named field, and creating the settings on
+///   first access.
+/// * `eq_by_schema`: compare and hash by schema id and path.
+/// * `accessor_trait` and `settings_ext`: a `${Struct}Ext` trait implemented by
+///   the struct, or by `gio::Settings`.
+/// * `values_struct`: a `${Struct}Values` snapshot with `values` and `set_all`.
+/// * `describe`: a `${Struct}KeyDescriptor` for each key.
+/// * `functions_module`: free functions taking a `&gio::Settings`.
+/// * `object`: a `${Struct}Object` with a property for each key.
+/// * `gtk_crate`: a `${key}_expression` for each key.
+/// * `assert_not_send`: a compile-time assertion that the struct is not `Send`.
+/// * `must_use_getters`, `try_getters`, `gstring_getters`,
+///   `string_array_iter`, `string_array_into_iter`,
+///   `connect_changed_with_old_value`, and `connect_changed_debounced`:
+///   variants of the methods of each key.
+///
 /// [`gio::Settings`]: https://docs.rs/gio/latest/gio/struct.Settings.html
 /// [`ToVariant`]: https://docs.rs/glib/latest/glib/variant/trait.ToVariant.html
 /// [`FromVariant`]: https://docs.rs/glib/latest/glib/variant/trait.FromVariant.html
+/// [`Variant`]: https://docs.rs/glib/latest/glib/variant/struct.Variant.html
+/// [`Handle`]: https://docs.rs/glib/latest/glib/variant/struct.Handle.html
+/// [`ValueDelegate`]: https://docs.rs/glib/latest/glib/derive.ValueDelegate.html
 /// [`bitflags`]: https://docs.rs/bitflags/latest/bitflags/macro.bitflags.html
 #[proc_macro_attribute]
 #[proc_macro_error]
@@ -285,154 +754,211 @@ pub fn gen_settings(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let GenSettings {
-        file: file_attr,
-        id: id_attr,
-    } = match deluxe::parse2(attr.into()) {
+    let mut gen_settings = match deluxe::parse2::<GenSettings>(attr.into()) {
         Ok(gen_settings) => gen_settings,
         Err(err) => return err.to_compile_error().into(),
     };
-    let file_attr_span = file_attr.span();
-    let schema_file_path = SpannedValue::into_inner(file_attr);
-
-    // Parse schema list
-    let schema_file = File::open(&schema_file_path).unwrap_or_else(|err| {
-        abort!(file_attr_span, "failed to open schema file: {}", err);
-    });
-    let schema_list: SchemaList = quick_xml::de::from_reader(BufReader::new(schema_file))
-        .unwrap_or_else(|err| abort!(file_attr_span, "failed to parse schema file: {}", err));
-
-    // Get first schema
-    let mut schemas = schema_list.schemas;
-    if schemas.len() > 1 {
-        emit_warning!(file_attr_span, "this macro only supports a single schema");
-    }
-    let schema = schemas
-        .pop()
-        .unwrap_or_else(|| abort!(file_attr_span, "schema file must have a single schema"));
-
-    // Get schema id
-    let schema_id = if let Some(id_attr) = id_attr {
-        let id_attr_span = id_attr.span();
-        let schema_id = SpannedValue::into_inner(id_attr);
+    let settings_struct = syn::parse_macro_input!(item as SettingsStruct);
 
-        if schema.id != schema_id {
-            emit_error!(
-                id_attr_span,
-                "id does not match the one specified in the schema file"
-            );
+    let (ids_attr, names_attr) = match (gen_settings.ids.take(), gen_settings.names.take()) {
+        (None, None) => {
+            let (expanded, _) =
+                expand_settings(gen_settings, settings_struct, None, &HashMap::new());
+            return expanded.into();
         }
-
-        Some(schema_id)
-    } else {
-        None
+        (Some(ids_attr), Some(names_attr)) => (ids_attr, names_attr),
+        _ => abort_call_site!("`ids` and `names` must be specified together"),
     };
+    let ids_attr_span = ids_attr.span();
+    let names_attr_span = names_attr.span();
+    let (ids, names) = (
+        SpannedValue::into_inner(ids_attr),
+        SpannedValue::into_inner(names_attr),
+    );
 
-    let settings_struct = syn::parse_macro_input!(item as SettingsStruct);
+    if let Some(ref id_attr) = gen_settings.id {
+        emit_error!(id_attr.span(), "`id` can't be specified along with `ids`");
+    }
+    if gen_settings.file.is_none() && gen_settings.xml.is_none() {
+        abort!(ids_attr_span, "`ids` requires either `file` or `xml`");
+    }
+    if ids.is_empty() {
+        abort!(ids_attr_span, "expected at least one id");
+    }
+    if ids.len() != names.len() {
+        abort!(
+            names_attr_span,
+            "expected as many names as ids, {}, but got {}",
+            ids.len(),
+            names.len()
+        );
+    }
 
-    // Parse overrides
-    let known_signatures = schema
-        .keys
-        .iter()
-        .map(|key| {
-            key.signature().unwrap_or_else(|| {
-                abort!(file_attr_span, "expected one of `type`, `enum` or `flags` specified attribute on key `{}` in the schema", key.name);
-            })
-        })
-        .collect::<Vec<_>>();
-    let known_key_names = schema
-        .keys
-        .iter()
-        .map(|key| key.name.as_str())
-        .collect::<Vec<_>>();
-    let mut signature_overrides = HashMap::new();
-    let mut key_name_overrides = HashMap::new();
-    for attr in &settings_struct.attrs {
-        let (signature, key_name, override_type) = if attr.path().is_ident("gen_settings_define") {
-            let GenSettingsDefine {
-                signature,
-                key_name,
-                arg_type,
-                ret_type,
-            } = match deluxe::parse_attributes::<_, GenSettingsDefine>(attr) {
-                Ok(gen_settings) => gen_settings,
-                Err(err) => {
-                    emit_error!(attr.span(), err);
-                    continue;
-                }
-            };
+    // The schema list is only parsed once and shared by all of the structs
+    let (schema_source_span, schema_list) = parse_schema_list(
+        gen_settings.file.take(),
+        gen_settings.dir.take(),
+        gen_settings.xml.take(),
+        None,
+    );
 
-            (
-                signature,
-                key_name,
-                OverrideType::Define {
-                    arg_type: SpannedValue::into_inner(arg_type),
-                    ret_type: SpannedValue::into_inner(ret_type),
-                },
-            )
-        } else if attr.path().is_ident("gen_settings_skip") {
-            let GenSettingsSkip {
-                signature,
-                key_name,
-            } = match deluxe::parse_attributes::<_, GenSettingsSkip>(attr) {
-                Ok(gen_settings) => gen_settings,
-                Err(err) => {
-                    emit_error!(attr.span(), err);
-                    continue;
-                }
-            };
+    let mut expanded = proc_macro2::TokenStream::new();
+    let mut emitted_aux_types = HashMap::new();
+    let mut seen_names = HashSet::new();
+    for (id, name) in ids.into_iter().zip(names) {
+        if !seen_names.insert(name.as_str().to_string()) {
+            emit_error!(name.span(), "duplicate struct name");
+            continue;
+        }
 
-            (signature, key_name, OverrideType::Skip)
-        } else {
+        let mut schema_list = schema_list.clone();
+        schema_list
+            .schemas
+            .retain(|schema| schema.id == id.as_str());
+        if schema_list.schemas.is_empty() {
             emit_error!(
-                attr.span(),
-                "expected `#[gen_settings_define( .. )]` or `#[gen_settings_skip( .. )]`"
+                id.span(),
+                "no schema with id `{}` found in the schema file",
+                id.as_str()
             );
             continue;
+        }
+
+        let mut settings_struct = settings_struct.clone();
+        settings_struct.ident = syn::parse_str::<syn::Ident>(name.as_str())
+            .unwrap_or_else(|err| abort!(name.span(), "invalid struct name: {}", err));
+        settings_struct.ident.set_span(name.span());
+
+        let gen_settings = GenSettings {
+            id: Some(id),
+            ..gen_settings.clone()
         };
+        let (struct_expanded, aux_types) = expand_settings(
+            gen_settings,
+            settings_struct,
+            Some((schema_source_span, schema_list)),
+            &emitted_aux_types,
+        );
+        expanded.extend(struct_expanded);
+        emitted_aux_types.extend(aux_types);
+    }
+    expanded.into()
+}
 
-        match (signature, key_name) {
-            (Some(_), Some(_)) => {
-                emit_error!(
-                    attr.span(),
-                    "cannot specify both `signature` and `key_name`"
-                )
-            }
-            (None, None) => {
-                emit_error!(attr.span(), "must specify either `signature` or `key_name`")
-            }
-            (Some(signature), None) => {
-                let signature_span = signature.span();
-                let signature_str = SpannedValue::into_inner(signature);
-                let signature_type = SchemaKeySignature::Type(signature_str);
+/// Generates the struct and the items of the schema, which is parsed from the
+/// attributes if `schema_source` is not specified.
+///
+/// The types generated for keys are only emitted if they are not in
+/// `emitted_aux_types`, which maps their names to their definitions, so the
+/// structs generated in one invocation can share them. The types generated
+/// by this struct are returned along with the expansion.
+fn expand_settings(
+    gen_settings: GenSettings,
+    mut settings_struct: SettingsStruct,
+    schema_source: Option<(proc_macro2::Span, SchemaList)>,
+    emitted_aux_types: &HashMap<String, String>,
+) -> (proc_macro2::TokenStream, HashMap<String, String>) {
+    let GenSettings {
+        file: file_attr,
+        dir: dir_attr,
+        xml: xml_attr,
+        id: id_attr,
+        ids: _,
+        names: _,
+        gio_crate: gio_crate_attr,
+        glib_crate: glib_crate_attr,
+        path: path_attr,
+        path_template: path_template_attr,
+        field_name: field_name_attr,
+        eq_by_schema: eq_by_schema_attr,
+        assert_not_send: assert_not_send_attr,
+        accessor_trait: accessor_trait_attr,
+        settings_ext: settings_ext_attr,
+        no_constructor: no_constructor_attr,
+        constructor_visibility: constructor_visibility_attr,
+        functions_module: functions_module_attr,
+        values_struct: values_struct_attr,
+        describe: describe_attr,
+        only: only_attr,
+        deny_unknown_keys: deny_unknown_keys_attr,
+        lazy: lazy_attr,
+        object: object_attr,
+        key_options,
+    } = gen_settings;
 
-                if !known_signatures.contains(&signature_type) {
-                    emit_error!(signature_span, "useless define for this signature");
-                }
+    let gio_crate = gio_crate_attr.map_or_else(|| syn::parse_quote!(gio), parse_crate_path);
+    let glib_crate =
+        glib_crate_attr.map_or_else(|| syn::parse_quote!(#gio_crate::glib), parse_crate_path);
+    let (gio, glib) = (&gio_crate, &glib_crate);
 
-                if signature_overrides.get(&signature_type).is_some() {
-                    emit_error!(signature_span, "duplicate override");
-                }
+    let (schema_source_span, schema_list) = schema_source
+        .unwrap_or_else(|| parse_schema_list(file_attr, dir_attr, xml_attr, id_attr.as_ref()));
 
-                signature_overrides.insert(signature_type, override_type);
-            }
-            (None, Some(key_name)) => {
-                let key_name_span = key_name.span();
-                let key_name_str = SpannedValue::into_inner(key_name);
+    // Get first schema
+    let mut schemas = schema_list.schemas;
+    if schemas.len() > 1 {
+        emit_error!(
+            schema_source_span,
+            "this macro only supports a single schema; use `ids` to generate a struct for each schema"
+        );
+    }
+    let mut schema = schemas
+        .pop()
+        .unwrap_or_else(|| abort!(schema_source_span, "schema file must have a single schema"));
 
-                if !known_key_names.contains(&key_name_str.as_str()) {
-                    emit_error!(key_name_span, "key_name not found in the schema");
-                }
+    if let Some(only_attr) = only_attr {
+        retain_only_keys(&mut schema, only_attr);
+    }
+    let schema_id = id_attr.map(|id_attr| parse_schema_id(id_attr, &schema));
+    let schema_path = path_attr.map(|path_attr| parse_schema_path(path_attr, &schema));
+    let schema_path_template = path_template_attr
+        .map(|path_template_attr| parse_schema_path_template(path_template_attr, &schema));
 
-                if key_name_overrides.get(&key_name_str).is_some() {
-                    emit_error!(key_name_span, "duplicate override");
-                }
+    let field_name = field_name_attr.map(|field_name_attr| {
+        let field_name_attr_span = field_name_attr.span();
+        syn::parse_str::<syn::Ident>(&SpannedValue::into_inner(field_name_attr)).unwrap_or_else(
+            |err| {
+                abort!(field_name_attr_span, "invalid field name: {}", err);
+            },
+        )
+    });
 
-                key_name_overrides.insert(key_name_str, override_type);
+    settings_struct.gio_crate = gio_crate.clone();
+    settings_struct.field_name = field_name.clone();
+    let mut field = Field::new(field_name);
+    // The settings can only be created on first access if the schema id and path
+    // are known before, as there is nothing to pass them with
+    if let Some(lazy_attr) = lazy_attr {
+        if *lazy_attr {
+            match schema_id {
+                Some(ref schema_id) if schema_path.is_some() || schema.path.is_some() => {
+                    settings_struct.is_lazy = true;
+                    field = field.with_lazy_init(if let Some(ref schema_path) = schema_path {
+                        quote! { #gio::Settings::with_path(#schema_id, #schema_path) }
+                    } else {
+                        quote! { #gio::Settings::new(#schema_id) }
+                    });
+                }
+                _ => emit_error!(
+                    lazy_attr.span(),
+                    "`lazy` requires the `id` attribute and a fixed path, either from the schema or the `path` attribute"
+                ),
             }
         }
     }
 
+    // Parse overrides
+    let known_signatures = schema
+        .keys
+        .iter()
+        .map(|key| {
+            key.parse_signature()
+                .unwrap_or_else(|err| abort!(schema_source_span, "{}", err))
+        })
+        .collect::<Vec<_>>();
+    check_range_types(&schema, schema_source_span);
+    let overrides = parse_overrides(&settings_struct.attrs, &schema, &known_signatures);
+
     // Generate keys
     let enums = schema_list
         .enums
@@ -444,26 +970,132 @@ pub fn gen_settings(
         .iter()
         .map(|flag| (flag.id.to_string(), flag))
         .collect::<HashMap<_, _>>();
-    let mut key_generators = KeyGenerators::with_defaults(enums, flags);
-    key_generators.add_signature_overrides(signature_overrides);
-    key_generators.add_key_name_overrides(key_name_overrides);
+    let mut key_generators = KeyGenerators::with_defaults(
+        enums,
+        flags,
+        Crates {
+            gio: gio_crate.clone(),
+            glib: glib_crate.clone(),
+        },
+        &field,
+    );
+    key_options.enable(&mut key_generators);
+    let key_name_skips = overrides.key_name_skips();
+    overrides.apply(&mut key_generators);
 
     // Generate code
-    let mut aux_token_stream = proc_macro2::TokenStream::new();
+    let struct_ident = &settings_struct.ident;
+    let struct_vis = &settings_struct.vis;
+    let crates = Crates {
+        gio: gio_crate.clone(),
+        glib: glib_crate.clone(),
+    };
+    let info = StructInfo {
+        ident: struct_ident,
+        vis: struct_vis,
+        field: &field,
+        crates: &crates,
+    };
+
+    let no_constructor = no_constructor_attr.unwrap_or(false);
+    let eq_by_schema = eq_by_schema_attr.unwrap_or(false);
+    let describe = describe_attr.unwrap_or(false);
+    let object = object_attr.unwrap_or(false);
+    let mut accessor_trait = accessor_trait_attr
+        .unwrap_or(false)
+        .then(AccessorTrait::default);
+    let settings_ext = settings_ext_attr.is_some_and(|settings_ext_attr| {
+        // Both traits would be named after the struct
+        if *settings_ext_attr && accessor_trait.is_some() {
+            emit_error!(
+                settings_ext_attr.span(),
+                "`settings_ext` can't be combined with `accessor_trait`, as both generate `{}Ext`",
+                struct_ident
+            );
+        }
+        *settings_ext_attr
+    });
+    let mut settings_ext_trait = settings_ext.then(SettingsExtTrait::default);
+    let mut functions_module = functions_module_attr.map(|attr| {
+        let attr_span = attr.span();
+        FunctionsModule::new(
+            syn::parse_str::<syn::Ident>(&SpannedValue::into_inner(attr)).unwrap_or_else(|err| {
+                abort!(attr_span, "invalid module name: {}", err);
+            }),
+        )
+    });
+    let mut values_struct = values_struct_attr
+        .unwrap_or(false)
+        .then(ValuesStruct::default);
+
+    let struct_name = struct_ident.to_string();
+    let mut struct_type_names = HashSet::new();
+    if !no_constructor {
+        struct_type_names.insert(format!("{}Builder", struct_name));
+    }
+    if accessor_trait.is_some() || settings_ext {
+        struct_type_names.insert(format!("{}Ext", struct_name));
+    }
+    if values_struct.is_some() {
+        struct_type_names.insert(format!("{}Values", struct_name));
+    }
+    if describe {
+        struct_type_names.insert(format!("{}KeyDescriptor", struct_name));
+    }
+    if object {
+        struct_type_names.insert(format!("{}Object", struct_name));
+        struct_type_names.insert(format!("{}ObjectImp", struct_name));
+    }
+    let mut aux_types = AuxiliaryTypes::new(emitted_aux_types, struct_name, struct_type_names);
+
     let mut keys_token_stream = proc_macro2::TokenStream::new();
+    let mut doc_hidden_keys_token_stream = proc_macro2::TokenStream::new();
+    let mut object_keys = Vec::new();
+    // The names generated for each key, which are checked once the methods of
+    // the struct are known. The object wrapper only has a property for each
+    // key, so it can't collide.
+    let mut impl_method_names = Vec::new();
 
     for key in &schema.keys {
         match key_generators
             .get(key, settings_struct.vis.clone())
             .unwrap()
         {
-            GetResult::Skip => (),
+            GetResult::Skip => {
+                if deny_unknown_keys_attr.unwrap_or(false) && !key_name_skips.contains(&key.name) {
+                    emit_call_site_error!(
+                        "key `{}` is skipped by its {} signature; with `deny_unknown_keys`, skip it with `#[gen_settings_skip(key_name = \"{}\")]` instead",
+                        &key.name,
+                        &key.signature().unwrap(),
+                        &key.name,
+                    );
+                }
+            }
             GetResult::Some(generator) => {
-                keys_token_stream.extend(generator.to_token_stream());
+                // Methods that are not documented with the key, such as the nick
+                // getter, are only hidden along with the impl block
+                let impl_items = generator.impl_items();
+                if generator.is_doc_hidden() {
+                    doc_hidden_keys_token_stream.extend(impl_items.tokens);
+                } else {
+                    keys_token_stream.extend(impl_items.tokens);
+                }
+                object_keys.push(key);
+                impl_method_names.push((key.name.as_str(), impl_items.names));
 
-                if let Some(aux) = generator.auxiliary() {
-                    aux_token_stream.extend(aux);
+                if let Some(ref mut functions_module) = functions_module {
+                    functions_module.push(&key.name, &generator, struct_ident);
+                }
+                if let Some(ref mut values_struct) = values_struct {
+                    values_struct.push(&generator);
+                }
+                if let Some(ref mut settings_ext_trait) = settings_ext_trait {
+                    settings_ext_trait.push(&key.name, &generator, struct_ident);
                 }
+                if let Some(ref mut accessor_trait) = accessor_trait {
+                    accessor_trait.push(&key.name, &generator);
+                }
+                aux_types.push(&key.name, &generator);
             }
             GetResult::Unknown => {
                 emit_call_site_error!(
@@ -475,65 +1107,159 @@ pub fn gen_settings(
         }
     }
 
-    let constructor_token_stream = if let Some(ref schema_id) = schema_id {
-        quote! {
-            pub fn new() -> Self {
-                Self(gio::Settings::new(#schema_id))
-            }
-        }
+    let constructor_vis = constructor_visibility_attr.map_or_else(
+        || syn::parse_quote!(pub),
+        |attr| {
+            let attr_span = attr.span();
+            syn::parse_str::<syn::Visibility>(&SpannedValue::into_inner(attr)).unwrap_or_else(
+                |err| {
+                    abort!(attr_span, "invalid visibility: {}", err);
+                },
+            )
+        },
+    );
+    // `From<gio::Settings>` can't be restricted, so it is only implemented if the
+    // constructors are public
+    let is_constructor_public = matches!(constructor_vis, syn::Visibility::Public(_));
+    let constructors = (!no_constructor).then(|| {
+        constructor::constructors(
+            &info,
+            &constructor_vis,
+            schema_id.as_deref(),
+            schema_path.as_deref(),
+            schema_path_template.as_deref(),
+            schema.path.is_none(),
+        )
+    });
+    let constructor_methods = constructors
+        .as_ref()
+        .map(|constructors| &constructors.methods);
+    let constructor_items = constructors
+        .as_ref()
+        .map(|constructors| &constructors.items);
+
+    // `ValueDelegate` can only delegate to the field of a tuple struct, so
+    // otherwise, or if the field is a `OnceCell`, it has to delegate through
+    // `From` conversions.
+    let value_delegate_attr = if field.is_named() || field.is_lazy() {
+        quote! { #[value_delegate(from = #gio::Settings, nullable)] }
+    } else {
+        quote! { #[value_delegate(nullable)] }
+    };
+
+    // A `OnceCell` can't be hashed, and comparing it would create the settings
+    let derive_attr = if eq_by_schema || field.is_lazy() {
+        quote! { #[derive(Clone, #glib::ValueDelegate)] }
+    } else {
+        quote! { #[derive(Clone, Hash, PartialEq, Eq, #glib::ValueDelegate)] }
+    };
+
+    let doc_hidden_keys_impl = if doc_hidden_keys_token_stream.is_empty() {
+        quote! {}
     } else {
         quote! {
-            pub fn new(schema_id: &str) -> Self {
-                Self(gio::Settings::new(schema_id))
+            #[doc(hidden)]
+            impl #struct_ident {
+                #doc_hidden_keys_token_stream
             }
         }
     };
 
-    let struct_ident = &settings_struct.ident;
-
+    let aux_token_stream = &aux_types.token_stream;
+    let common_methods = impls::common_methods(
+        &info,
+        &constructor_vis,
+        schema.keys.iter().map(|key| key.name.as_str()),
+    );
+    let settings_impls = impls::settings_impls(&info);
     let mut expanded = quote! {
         #aux_token_stream
 
-        #[derive(Clone, Hash, PartialEq, Eq, gio::glib::ValueDelegate)]
-        #[value_delegate(nullable)]
+        #constructor_items
+
+        #derive_attr
+        #value_delegate_attr
         #settings_struct
 
         impl #struct_ident {
-            #constructor_token_stream
+            #constructor_methods
+
+            #common_methods
 
             #keys_token_stream
         }
 
-        impl std::ops::Deref for #struct_ident {
-            type Target = gio::Settings;
+        #doc_hidden_keys_impl
 
-            fn deref(&self) -> &Self::Target {
-                &self.0
-            }
-        }
+        #settings_impls
+    };
 
-        impl std::ops::DerefMut for #struct_ident {
-            fn deref_mut(&mut self) -> &mut Self::Target {
-                &mut self.0
-            }
-        }
+    if assert_not_send_attr.unwrap_or(false) {
+        expanded.extend(impls::assert_not_send(struct_ident));
+    }
 
-        impl std::fmt::Debug for #struct_ident {
-            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                std::fmt::Debug::fmt(&self.0, f)
-            }
-        }
-    };
+    if let Some(ref accessor_trait) = accessor_trait {
+        expanded.extend(accessor_trait.token_stream(&info));
+    }
 
-    if schema_id.is_some() {
-        expanded.extend(quote! {
-            impl Default for #struct_ident {
-                fn default() -> Self {
-                    Self::new()
-                }
-            }
-        });
+    if let Some(ref settings_ext_trait) = settings_ext_trait {
+        expanded.extend(settings_ext_trait.token_stream(&info));
     }
 
-    expanded.into()
+    if let Some(ref values_struct) = values_struct {
+        expanded.extend(values_struct.token_stream(&info));
+    }
+
+    if describe {
+        expanded.extend(describe::describe_token_stream(&info, &schema.keys));
+    }
+
+    if let Some(ref functions_module) = functions_module {
+        expanded.extend(functions_module.token_stream(&info));
+    }
+
+    if object {
+        expanded.extend(object::object_token_stream(
+            struct_ident,
+            struct_vis,
+            &schema.id,
+            &object_keys,
+            &crates,
+        ));
+    }
+
+    if eq_by_schema {
+        expanded.extend(impls::eq_by_schema_impls(&info));
+    }
+
+    if field.is_named() || field.is_lazy() {
+        expanded.extend(impls::from_impls(&info, is_constructor_public));
+    }
+
+    let mut reserved_method_names = RESERVED_METHOD_NAMES
+        .iter()
+        .copied()
+        .collect::<HashSet<_>>();
+    if let Some(ref constructors) = constructors {
+        reserved_method_names.extend(constructors.method_names.iter().copied());
+    }
+    if values_struct.is_some() {
+        reserved_method_names.extend(ValuesStruct::METHOD_NAMES.iter().copied());
+    }
+    if describe {
+        reserved_method_names.insert("describe");
+    }
+
+    check_method_names("the struct", impl_method_names, &reserved_method_names);
+    if let Some(functions_module) = functions_module {
+        functions_module.check_method_names();
+    }
+    if let Some(settings_ext_trait) = settings_ext_trait {
+        settings_ext_trait.check_method_names(struct_ident);
+    }
+    if let Some(accessor_trait) = accessor_trait {
+        accessor_trait.check_method_names(struct_ident);
+    }
+
+    (expanded, aux_types.definitions)
 }
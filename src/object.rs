@@ -0,0 +1,254 @@
+use heck::ToPascalCase;
+use quote::{format_ident, quote};
+use syn::Ident;
+
+use crate::{
+    generators::{value_types, Crates},
+    schema::Key as SchemaKey,
+};
+
+/// The property of the generated object that mirrors a key
+struct Property {
+    param_spec: proc_macro2::TokenStream,
+    getter: proc_macro2::TokenStream,
+    setter: proc_macro2::TokenStream,
+}
+
+impl Property {
+    fn new(key: &SchemaKey, crates: &Crates) -> Self {
+        let Crates { glib, .. } = crates;
+        let key_name = key.name.as_str();
+        let blurb = key.summary.as_deref().unwrap_or_default();
+
+        // The properties hold the stored values, so enums are exposed as their
+        // nicks and flags as arrays of their nicks
+        let storage_type = key
+            .signature()
+            .expect("signatures are validated before generating")
+            .storage_type()
+            .to_string();
+
+        // Keys with a basic type are exposed with the matching fundamental
+        // type, so they can be bound to widget properties, while the rest are
+        // exposed as variants.
+        if let Some((stored_type, widened_type)) = value_types(&storage_type, crates) {
+            let value_type = widened_type.as_ref().unwrap_or(&stored_type);
+
+            // Setting a string property to `None` resets the key
+            let setter = if storage_type == "s" {
+                quote! {
+                    match value.get::<Option<String>>().expect("values must match the type of the property") {
+                        Some(value) => settings.set(#key_name, value),
+                        None => {
+                            settings.reset(#key_name);
+                            Ok(())
+                        }
+                    }
+                }
+            } else if widened_type.is_some() {
+                // The range of the param spec ensures that the conversion succeeds
+                quote! {
+                    settings.set(
+                        #key_name,
+                        #stored_type::try_from(
+                            value.get::<#value_type>().expect("values must match the type of the property"),
+                        )
+                        .expect("values must be within the range of the property"),
+                    )
+                }
+            } else {
+                quote! {
+                    settings.set(
+                        #key_name,
+                        value.get::<#value_type>().expect("values must match the type of the property"),
+                    )
+                }
+            };
+
+            let (range, getter) = if widened_type.is_some() {
+                (
+                    quote! {
+                        .minimum(#value_type::from(#stored_type::MIN))
+                        .maximum(#value_type::from(#stored_type::MAX))
+                    },
+                    quote! { #value_type::from(settings.get::<#stored_type>(#key_name)).to_value() },
+                )
+            } else {
+                (
+                    quote! {},
+                    quote! { settings.get::<#stored_type>(#key_name).to_value() },
+                )
+            };
+
+            Self {
+                param_spec: quote! {
+                    (<#value_type as #glib::HasParamSpec>::param_spec_builder())(#key_name)
+                        .blurb(#blurb)
+                        #range
+                        .explicit_notify()
+                        .build()
+                },
+                getter,
+                setter,
+            }
+        } else {
+            Self {
+                param_spec: quote! {
+                    #glib::ParamSpecVariant::builder(
+                        #key_name,
+                        #glib::VariantTy::new(#storage_type).unwrap(),
+                    )
+                    .blurb(#blurb)
+                    .explicit_notify()
+                    .build()
+                },
+                getter: quote! { settings.value(#key_name).to_value() },
+                setter: quote! {
+                    match value.get::<Option<#glib::Variant>>().expect("values must match the type of the property") {
+                        Some(value) => settings.set_value(#key_name, &value),
+                        None => {
+                            settings.reset(#key_name);
+                            Ok(())
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Generates `${Struct}Object`, a [`glib::Object`] subclass with a property
+/// for each of the given keys, which is synced with the settings.
+///
+/// The name of the registered type is prefixed with the schema id, so structs
+/// with the same name for different schemas don't clash. The module path is not
+/// available to the macro, so structs with the same name for the same schema
+/// in different modules do clash.
+pub fn object_token_stream(
+    struct_ident: &Ident,
+    struct_vis: &syn::Visibility,
+    schema_id: &str,
+    keys: &[&SchemaKey],
+    crates: &Crates,
+) -> proc_macro2::TokenStream {
+    let Crates { gio, glib } = crates;
+
+    let object_ident = format_ident!("{}Object", struct_ident);
+    let imp_ident = format_ident!("{}ObjectImp", struct_ident);
+    let type_name = format!("{}{}", schema_id.to_pascal_case(), object_ident);
+    let object_docs = format!(
+        "A `glib::Object` with a property for each key of [`{}`], named after the key.\n\n\
+        Setting a property writes the key, and a change of the key notifies the property.",
+        struct_ident
+    );
+
+    let key_names = keys.iter().map(|key| key.name.as_str()).collect::<Vec<_>>();
+    let properties = keys
+        .iter()
+        .map(|key| Property::new(key, crates))
+        .collect::<Vec<_>>();
+    let param_specs = properties.iter().map(|property| &property.param_spec);
+    let getters = properties.iter().map(|property| &property.getter);
+    let setters = properties.iter().map(|property| &property.setter);
+
+    quote! {
+        #[doc(hidden)]
+        #[derive(Default)]
+        #struct_vis struct #imp_ident {
+            settings: std::cell::OnceCell<#gio::Settings>,
+            changed_handler_id: std::cell::RefCell<Option<#glib::SignalHandlerId>>,
+        }
+
+        #[#glib::object_subclass]
+        impl #glib::subclass::types::ObjectSubclass for #imp_ident {
+            const NAME: &'static str = #type_name;
+            type Type = #object_ident;
+            type ParentType = #glib::Object;
+        }
+
+        impl #glib::subclass::object::ObjectImpl for #imp_ident {
+            fn properties() -> &'static [#glib::ParamSpec] {
+                use #glib::prelude::*;
+
+                static PROPERTIES: std::sync::OnceLock<Vec<#glib::ParamSpec>> =
+                    std::sync::OnceLock::new();
+                PROPERTIES.get_or_init(|| vec![#(#param_specs),*])
+            }
+
+            fn set_property(&self, _id: usize, value: &#glib::Value, pspec: &#glib::ParamSpec) {
+                use #gio::prelude::*;
+
+                let settings = self.settings.get().expect("settings must be set on construction");
+                let result: std::result::Result<(), #glib::BoolError> = match pspec.name() {
+                    #(#key_names => #setters,)*
+                    _ => unreachable!(),
+                };
+                if let Err(err) = result {
+                    #glib::g_critical!(
+                        "gsettings-macro",
+                        "failed to set key `{}`: {}",
+                        pspec.name(),
+                        err
+                    );
+                }
+            }
+
+            fn property(&self, _id: usize, pspec: &#glib::ParamSpec) -> #glib::Value {
+                use #gio::prelude::*;
+                use #glib::value::ToValue;
+
+                let settings = self.settings.get().expect("settings must be set on construction");
+                match pspec.name() {
+                    #(#key_names => #getters,)*
+                    _ => unreachable!(),
+                }
+            }
+
+            fn dispose(&self) {
+                if let (Some(settings), Some(handler_id)) =
+                    (self.settings.get(), self.changed_handler_id.take())
+                {
+                    #glib::prelude::ObjectExt::disconnect(settings, handler_id);
+                }
+            }
+        }
+
+        #glib::wrapper! {
+            #[doc = #object_docs]
+            #struct_vis struct #object_ident(ObjectSubclass<#imp_ident>);
+        }
+
+        impl #object_ident {
+            /// Creates an object whose properties are synced with `settings`.
+            #struct_vis fn new(settings: &#struct_ident) -> Self {
+                let obj = #glib::Object::new::<Self>();
+                let imp = #glib::subclass::prelude::ObjectSubclassIsExt::imp(&obj);
+
+                let settings = #gio::Settings::clone(settings);
+                let obj_weak = #glib::prelude::ObjectExt::downgrade(&obj);
+                let changed_handler_id =
+                    #gio::prelude::SettingsExt::connect_changed(&settings, None, move |_, key| {
+                        let Some(obj) = obj_weak.upgrade() else {
+                            return;
+                        };
+
+                        // Skipped keys have no property
+                        if let Some(pspec) = #glib::prelude::ObjectExt::find_property(&obj, key) {
+                            #glib::prelude::ObjectExt::notify_by_pspec(&obj, &pspec);
+                        }
+                    });
+
+                imp.settings.set(settings).unwrap();
+                imp.changed_handler_id.replace(Some(changed_handler_id));
+
+                obj
+            }
+
+            /// Returns the settings the properties are synced with.
+            #struct_vis fn settings(&self) -> #gio::Settings {
+                let imp = #glib::subclass::prelude::ObjectSubclassIsExt::imp(self);
+                #gio::Settings::clone(imp.settings.get().expect("settings must be set on construction"))
+            }
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use deluxe::SpannedValue;
+use proc_macro_error::emit_error;
+use syn::spanned::Spanned;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    generators::{ContainerKind, KeyGenerators, OverrideType},
+    schema::{KeySignature as SchemaKeySignature, Schema},
+};
+
+#[derive(deluxe::ParseAttributes)]
+struct GenSettingsDefine {
+    signature: Option<SpannedValue<String>>,
+    key_name: Option<SpannedValue<String>>,
+    arg_type: Option<SpannedValue<String>>,
+    ret_type: Option<SpannedValue<String>>,
+    element_arg_type: Option<SpannedValue<String>>,
+    element_ret_type: Option<SpannedValue<String>>,
+    enum_name: Option<SpannedValue<String>>,
+    doc_hidden: Option<SpannedValue<bool>>,
+    force: Option<SpannedValue<bool>>,
+}
+
+#[derive(deluxe::ParseAttributes)]
+struct GenSettingsSkip {
+    signature: Option<SpannedValue<String>>,
+    key_name: Option<SpannedValue<String>>,
+    kind: Option<SpannedValue<String>>,
+}
+
+pub fn is_helper_attr(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("gen_settings_define") || attr.path().is_ident("gen_settings_skip")
+}
+
+/// The overrides specified with `#[gen_settings_define( .. )]` and
+/// `#[gen_settings_skip( .. )]`
+pub struct Overrides {
+    signatures: HashMap<SchemaKeySignature, OverrideType>,
+    key_names: HashMap<String, OverrideType>,
+    enum_names: HashMap<String, String>,
+    doc_hidden_keys: HashSet<String>,
+    kind_skips: HashSet<ContainerKind>,
+}
+
+impl Overrides {
+    /// Names of the keys that are skipped by name, as opposed to the keys skipped
+    /// by their signature or kind, which are not consciously handled one by one
+    pub fn key_name_skips(&self) -> HashSet<String> {
+        self.key_names
+            .iter()
+            .filter(|(_, override_type)| matches!(override_type, OverrideType::Skip))
+            .map(|(key_name, _)| key_name.clone())
+            .collect()
+    }
+
+    pub fn apply(self, key_generators: &mut KeyGenerators<'_>) {
+        key_generators.add_signature_overrides(self.signatures);
+        key_generators.add_key_name_overrides(self.key_names);
+        key_generators.add_enum_name_overrides(self.enum_names);
+        key_generators.add_doc_hidden_keys(self.doc_hidden_keys);
+        key_generators.add_kind_skips(self.kind_skips);
+    }
+}
+
+/// Parses the overrides from the helper attributes of the struct, emitting an
+/// error for each one that is invalid or not applied on any key. The other
+/// attributes are emitted as is on the generated struct.
+pub fn parse_overrides(
+    attrs: &[syn::Attribute],
+    schema: &Schema,
+    known_signatures: &[SchemaKeySignature],
+) -> Overrides {
+    let known_key_names = schema
+        .keys
+        .iter()
+        .map(|key| key.name.as_str())
+        .collect::<Vec<_>>();
+    let mut signature_overrides = HashMap::new();
+    let mut signature_override_spans = HashMap::new();
+    let mut key_name_overrides = HashMap::new();
+    let mut enum_name_overrides = HashMap::new();
+    let mut doc_hidden_keys = HashSet::new();
+    let mut kind_skips = HashSet::new();
+    for attr in attrs {
+        let (signature, key_name, override_type) = if attr.path().is_ident("gen_settings_define") {
+            let GenSettingsDefine {
+                signature,
+                key_name,
+                arg_type,
+                ret_type,
+                element_arg_type,
+                element_ret_type,
+                enum_name,
+                doc_hidden,
+                force,
+            } = match deluxe::parse_attributes::<_, GenSettingsDefine>(attr) {
+                Ok(gen_settings) => gen_settings,
+                Err(err) => {
+                    emit_error!(attr.span(), err);
+                    continue;
+                }
+            };
+
+            // Hiding the methods from the docs can be combined with the other
+            // defines of the key, or be the only define
+            if let Some(doc_hidden) = doc_hidden {
+                let doc_hidden_span = doc_hidden.span();
+
+                let Some(ref key_name) = key_name else {
+                    emit_error!(
+                        doc_hidden_span,
+                        "`doc_hidden` can only be used with `key_name`"
+                    );
+                    continue;
+                };
+                if !known_key_names.contains(&key_name.as_str()) {
+                    emit_error!(key_name.span(), "key_name not found in the schema");
+                    continue;
+                }
+
+                if SpannedValue::into_inner(doc_hidden)
+                    && !doc_hidden_keys.insert(key_name.as_str().to_string())
+                {
+                    emit_error!(key_name.span(), "duplicate override");
+                }
+
+                if [
+                    &arg_type,
+                    &ret_type,
+                    &element_arg_type,
+                    &element_ret_type,
+                    &enum_name,
+                ]
+                .iter()
+                .all(|attr| attr.is_none())
+                    && force.is_none()
+                {
+                    continue;
+                }
+            }
+
+            // Renaming the generated type keeps the built in methods, so it is
+            // not a type override
+            if let Some(enum_name) = enum_name {
+                let enum_name_span = enum_name.span();
+
+                let Some(key_name) = key_name else {
+                    emit_error!(
+                        enum_name_span,
+                        "`enum_name` can only be used with `key_name`"
+                    );
+                    continue;
+                };
+                if [
+                    &signature,
+                    &arg_type,
+                    &ret_type,
+                    &element_arg_type,
+                    &element_ret_type,
+                ]
+                .iter()
+                .any(|attr| attr.is_some())
+                    || force.is_some()
+                {
+                    emit_error!(
+                        attr.span(),
+                        "`enum_name` cannot be combined with other defines"
+                    );
+                    continue;
+                }
+
+                let Some(key) = schema.keys.iter().find(|key| key.name == key_name.as_str()) else {
+                    emit_error!(key_name.span(), "key_name not found in the schema");
+                    continue;
+                };
+                let has_generated_type = key.choices.is_some()
+                    || matches!(
+                        key.signature(),
+                        Some(SchemaKeySignature::Enum(_) | SchemaKeySignature::Flag(_))
+                    );
+                if !has_generated_type {
+                    emit_error!(
+                        enum_name_span,
+                        "`enum_name` can only be used for keys with an enum, flags, or choices"
+                    );
+                }
+
+                if let Err(err) = syn::parse_str::<syn::Ident>(enum_name.as_str()) {
+                    emit_error!(enum_name_span, "invalid enum name: {}", err);
+                    continue;
+                }
+
+                if enum_name_overrides.contains_key(key_name.as_str()) {
+                    emit_error!(key_name.span(), "duplicate override");
+                }
+
+                enum_name_overrides.insert(
+                    SpannedValue::into_inner(key_name),
+                    SpannedValue::into_inner(enum_name),
+                );
+                continue;
+            }
+
+            if let (Some(ref force), Some(_)) = (&force, &key_name) {
+                emit_error!(force.span(), "`force` can only be used with `signature`");
+            }
+
+            let mut has_invalid_type = false;
+            for type_ in [&arg_type, &ret_type, &element_arg_type, &element_ret_type]
+                .into_iter()
+                .flatten()
+            {
+                if let Err(err) = syn::parse_str::<syn::Type>(type_.as_str()) {
+                    emit_error!(type_.span(), "invalid type: {}", err);
+                    has_invalid_type = true;
+                }
+            }
+            if has_invalid_type {
+                continue;
+            }
+
+            let (arg_type, ret_type) = match (
+                arg_type,
+                ret_type,
+                element_arg_type,
+                element_ret_type,
+            ) {
+                (Some(arg_type), Some(ret_type), None, None) => (
+                    SpannedValue::into_inner(arg_type),
+                    SpannedValue::into_inner(ret_type),
+                ),
+                (None, None, Some(element_arg_type), Some(element_ret_type)) => {
+                    let define_signature = match (&signature, &key_name) {
+                        (Some(signature), _) => Some(signature.as_str().to_string()),
+                        (None, Some(key_name)) => schema
+                            .keys
+                            .iter()
+                            .find(|key| key.name == key_name.as_str())
+                            .and_then(|key| match key.signature() {
+                                Some(SchemaKeySignature::Type(type_)) => Some(type_),
+                                _ => None,
+                            }),
+                        (None, None) => None,
+                    };
+                    let is_array = define_signature.is_some_and(|signature| {
+                        signature.starts_with('a') && !signature.starts_with("a{")
+                    });
+                    if !is_array {
+                        emit_error!(
+                            element_arg_type.span(),
+                            "element types can only be defined for array type signatures"
+                        );
+                    }
+
+                    // Same as the built in array types
+                    (
+                        format!("&[{}]", element_arg_type.as_str()),
+                        format!("Vec<{}>", element_ret_type.as_str()),
+                    )
+                }
+                _ => {
+                    emit_error!(
+                        attr.span(),
+                        "must specify either `arg_type` and `ret_type`, or `element_arg_type` and `element_ret_type`"
+                    );
+                    continue;
+                }
+            };
+
+            (
+                signature,
+                key_name,
+                OverrideType::Define {
+                    arg_type,
+                    ret_type,
+                    force: force.is_some_and(SpannedValue::into_inner),
+                },
+            )
+        } else if attr.path().is_ident("gen_settings_skip") {
+            let GenSettingsSkip {
+                signature,
+                key_name,
+                kind,
+            } = match deluxe::parse_attributes::<_, GenSettingsSkip>(attr) {
+                Ok(gen_settings) => gen_settings,
+                Err(err) => {
+                    emit_error!(attr.span(), err);
+                    continue;
+                }
+            };
+
+            if let Some(kind) = kind {
+                let kind_span = kind.span();
+
+                if signature.is_some() || key_name.is_some() {
+                    emit_error!(
+                        attr.span(),
+                        "cannot specify `kind` with `signature` or `key_name`"
+                    );
+                    continue;
+                }
+
+                let kind = match kind.parse::<ContainerKind>() {
+                    Ok(kind) => kind,
+                    Err(err) => {
+                        emit_error!(kind_span, "{}", err);
+                        continue;
+                    }
+                };
+
+                if !known_signatures
+                    .iter()
+                    .any(|signature| ContainerKind::of(signature) == Some(kind))
+                {
+                    emit_error!(kind_span, "useless skip for this kind");
+                }
+
+                if !kind_skips.insert(kind) {
+                    emit_error!(kind_span, "duplicate override");
+                }
+                continue;
+            }
+
+            (signature, key_name, OverrideType::Skip)
+        } else {
+            // Other attributes are emitted as is on the generated struct
+            continue;
+        };
+
+        match (signature, key_name) {
+            (Some(_), Some(_)) => {
+                emit_error!(
+                    attr.span(),
+                    "cannot specify both `signature` and `key_name`"
+                )
+            }
+            (None, None) => {
+                emit_error!(attr.span(), "must specify either `signature` or `key_name`")
+            }
+            (Some(signature), None) => {
+                let signature_span = signature.span();
+                let signature_str = SpannedValue::into_inner(signature);
+                let signature_type = SchemaKeySignature::Type(signature_str);
+
+                if !known_signatures.contains(&signature_type) {
+                    emit_error!(signature_span, "useless define for this signature");
+                }
+
+                if signature_overrides.contains_key(&signature_type) {
+                    emit_error!(signature_span, "duplicate override");
+                }
+
+                signature_override_spans.insert(signature_type.clone(), signature_span);
+                signature_overrides.insert(signature_type, override_type);
+            }
+            (None, Some(key_name)) => {
+                let key_name_span = key_name.span();
+                let key_name_str = SpannedValue::into_inner(key_name);
+
+                if !known_key_names.contains(&key_name_str.as_str()) {
+                    emit_error!(key_name_span, "key_name not found in the schema");
+                }
+
+                if key_name_overrides.contains_key(&key_name_str) {
+                    emit_error!(key_name_span, "duplicate override");
+                }
+
+                key_name_overrides.insert(key_name_str, override_type);
+            }
+        }
+    }
+
+    // Check that the signature overrides are applied on at least one key, as
+    // they could be shadowed by key name overrides or keys with choices
+    for (signature, override_type) in &signature_overrides {
+        let is_applied = schema.keys.iter().any(|key| {
+            let applies_to_choices = match override_type {
+                OverrideType::Define { force, .. } => *force,
+                OverrideType::Skip => true,
+            };
+
+            key.signature().as_ref() == Some(signature)
+                && !key_name_overrides.contains_key(&key.name)
+                && (key.choices.is_none() || applies_to_choices)
+        });
+
+        if !is_applied && known_signatures.contains(signature) {
+            emit_error!(
+                signature_override_spans[signature],
+                "override is not applied on any key; all keys with this signature have choices or are overridden by `key_name`"
+            );
+        }
+    }
+
+    Overrides {
+        signatures: signature_overrides,
+        key_names: key_name_overrides,
+        enum_names: enum_name_overrides,
+        doc_hidden_keys,
+        kind_skips,
+    }
+}
@@ -1,16 +1,187 @@
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize)]
+use std::{
+    borrow::Cow,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Returns the paths of the schema files in `dir`, sorted by their file name.
+pub fn schema_files_in_dir(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        let is_schema_file = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .is_some_and(|file_name| file_name.ends_with(".gschema.xml"));
+        if is_schema_file && path.is_file() {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+    Ok(paths)
+}
+
+/// Returns the paths of the `*.enums.xml` files in `dir`, sorted by their file
+/// name. These are installed alongside the schema files by `glib-mkenums`.
+pub fn enum_files_in_dir(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        let is_enum_file = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .is_some_and(|file_name| file_name.ends_with(".enums.xml"));
+        if is_enum_file && path.is_file() {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+    Ok(paths)
+}
+
+/// Returns the directories that make up the default schema source, in the
+/// order GIO consults them: `$GSETTINGS_SCHEMA_DIR`, then
+/// `$XDG_DATA_HOME/glib-2.0/schemas`, then `glib-2.0/schemas` in each of
+/// `$XDG_DATA_DIRS`.
+pub fn installed_schema_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Some(schema_dirs) = env::var_os("GSETTINGS_SCHEMA_DIR") {
+        dirs.extend(env::split_paths(&schema_dirs));
+    }
+
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .filter(|data_home| !data_home.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")));
+    let data_dirs = env::var_os("XDG_DATA_DIRS")
+        .filter(|data_dirs| !data_dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+    dirs.extend(
+        data_home
+            .into_iter()
+            .chain(env::split_paths(&data_dirs))
+            .map(|data_dir| data_dir.join("glib-2.0/schemas")),
+    );
+
+    dirs
+}
+
+#[derive(Clone, Debug, Deserialize)]
 pub struct SchemaList {
     #[serde(rename = "enum", default)]
     pub enums: Vec<Enum>,
     #[serde(default)]
     pub flags: Vec<Flag>,
-    #[serde(rename = "schema")]
+    #[serde(rename = "schema", default)]
     pub schemas: Vec<Schema>,
 }
 
-#[derive(Debug, Deserialize)]
+impl SchemaList {
+    /// Parses a schema file from its raw contents.
+    ///
+    /// The contents are decoded as UTF-8, unless they start with a UTF-16 byte
+    /// order mark. A leading byte order mark is not part of the document.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let xml = if let Some(rest) = bytes.strip_prefix(b"\xFF\xFE") {
+            decode_utf16(rest, u16::from_le_bytes)?
+        } else if let Some(rest) = bytes.strip_prefix(b"\xFE\xFF") {
+            decode_utf16(rest, u16::from_be_bytes)?
+        } else {
+            let rest = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+            String::from_utf8(rest.to_vec()).map_err(|err| {
+                let valid_up_to = err.utf8_error().valid_up_to();
+                let line = rest[..valid_up_to]
+                    .iter()
+                    .filter(|byte| **byte == b'\n')
+                    .count()
+                    + 1;
+                format!("invalid UTF-8 at byte {} (line {})", valid_up_to, line)
+            })?
+        };
+
+        Self::from_xml(&xml)
+    }
+
+    /// Parses a schema file from its contents, ignoring a leading byte order mark.
+    pub fn from_xml(xml: &str) -> Result<Self, String> {
+        let xml = xml.strip_prefix('\u{FEFF}').unwrap_or(xml);
+        quick_xml::de::from_str(xml).map_err(|err| err.to_string())
+    }
+
+    /// Merges the enums, flags, and schemas of `other` into this list.
+    ///
+    /// Schemas with the same id are merged into one, where it is an error for
+    /// both to define the same key or to have different paths. It is also an error
+    /// to define an enum or flag with the same id.
+    pub fn merge(&mut self, other: SchemaList) -> Result<(), String> {
+        for enum_ in other.enums {
+            if self
+                .enums
+                .iter()
+                .any(|other_enum| other_enum.id == enum_.id)
+            {
+                return Err(format!("enum `{}` is defined more than once", enum_.id));
+            }
+            self.enums.push(enum_);
+        }
+
+        for flag in other.flags {
+            if self.flags.iter().any(|other_flag| other_flag.id == flag.id) {
+                return Err(format!("flags `{}` is defined more than once", flag.id));
+            }
+            self.flags.push(flag);
+        }
+
+        for schema in other.schemas {
+            let Some(existing) = self
+                .schemas
+                .iter_mut()
+                .find(|existing| existing.id == schema.id)
+            else {
+                self.schemas.push(schema);
+                continue;
+            };
+
+            match (&existing.path, schema.path) {
+                (Some(path), Some(other_path)) if *path != other_path => {
+                    return Err(format!(
+                        "schema `{}` has conflicting paths `{}` and `{}`",
+                        schema.id, path, other_path
+                    ));
+                }
+                (None, Some(other_path)) => existing.path = Some(other_path),
+                _ => (),
+            }
+
+            for key in schema.keys {
+                if existing
+                    .keys
+                    .iter()
+                    .any(|other_key| other_key.name == key.name)
+                {
+                    return Err(format!(
+                        "key `{}` of schema `{}` is defined more than once",
+                        key.name, schema.id
+                    ));
+                }
+                existing.keys.push(key);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
 pub struct Enum {
     #[serde(rename = "@id")]
     pub id: String,
@@ -18,7 +189,7 @@ pub struct Enum {
     pub values: Vec<EnumValues>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct EnumValues {
     #[serde(rename = "@nick")]
     pub nick: String,
@@ -26,7 +197,7 @@ pub struct EnumValues {
     pub value: i32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct Flag {
     #[serde(rename = "@id")]
     pub id: String,
@@ -34,7 +205,7 @@ pub struct Flag {
     pub values: Vec<FlagValues>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct FlagValues {
     #[serde(rename = "@nick")]
     pub nick: String,
@@ -42,15 +213,17 @@ pub struct FlagValues {
     pub value: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct Schema {
     #[serde(rename = "@id")]
     pub id: String,
-    #[serde(rename = "key")]
+    #[serde(rename = "@path")]
+    pub path: Option<String>,
+    #[serde(rename = "key", default)]
     pub keys: Vec<Key>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct Key {
     #[serde(rename = "@name")]
     pub name: String,
@@ -64,16 +237,29 @@ pub struct Key {
     pub summary: Option<String>,
     pub description: Option<String>,
     pub choices: Option<Choices>,
+    pub aliases: Option<Aliases>,
     pub range: Option<Range>,
 }
 
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub enum KeySignature {
     Type(String),
     Enum(String),
     Flag(String),
 }
 
+impl KeySignature {
+    /// The type signature of the values stored for the key, where enums are
+    /// stored as strings and flags as arrays of strings.
+    pub fn storage_type(&self) -> &str {
+        match self {
+            KeySignature::Type(type_) => type_,
+            KeySignature::Enum(_) => "s",
+            KeySignature::Flag(_) => "as",
+        }
+    }
+}
+
 impl std::fmt::Display for KeySignature {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -85,32 +271,199 @@ impl std::fmt::Display for KeySignature {
 }
 
 impl Key {
+    /// The default value, without its leading type annotation, if any.
+    ///
+    /// For example, this would be `'x'` for `@ms 'x'`, and `[]` for `@as []`.
+    pub fn default_value(&self) -> &str {
+        let default = self.default.trim();
+
+        match default.strip_prefix('@') {
+            Some(annotated) => annotated
+                .split_once(char::is_whitespace)
+                .map_or("", |(_, value)| value.trim_start()),
+            None => default,
+        }
+    }
+
+    /// The default value as a string, if it is a quoted string, for example,
+    /// this would be `glass` for `'glass'` and `"glass"`.
+    ///
+    /// Escape sequences are unescaped as in the GVariant text format, so this
+    /// would be `It's here` for `'It\'s here'`, while quotes of the other kind
+    /// can be used as is, as in `"It's here"`.
+    ///
+    /// This is the nick of the default value of keys with enums or choices.
+    pub fn default_string(&self) -> Option<Cow<'_, str>> {
+        let default_value = self.default_value().trim();
+
+        let quote = default_value
+            .chars()
+            .next()
+            .filter(|c| matches!(c, '\'' | '"'))?;
+        let quoted = default_value[1..].strip_suffix(quote)?;
+
+        if !quoted.contains('\\') {
+            return (!quoted.contains(quote)).then_some(Cow::Borrowed(quoted));
+        }
+
+        let mut unescaped = String::with_capacity(quoted.len());
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            if c == quote {
+                // An unescaped quote ends the string before the end
+                return None;
+            }
+            if c != '\\' {
+                unescaped.push(c);
+                continue;
+            }
+
+            let escaped = match chars.next()? {
+                'a' => '\u{7}',
+                'b' => '\u{8}',
+                'f' => '\u{c}',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                'v' => '\u{b}',
+                prefix @ ('u' | 'U') => {
+                    let len = if prefix == 'u' { 4 } else { 8 };
+                    let digits = chars.by_ref().take(len).collect::<String>();
+                    if digits.len() != len {
+                        return None;
+                    }
+                    char::from_u32(u32::from_str_radix(&digits, 16).ok()?)?
+                }
+                // Any other character, including quotes and backslashes, is
+                // taken literally
+                other => other,
+            };
+            unescaped.push(escaped);
+        }
+
+        Some(Cow::Owned(unescaped))
+    }
+
+    /// The default value, like [`Key::default_value`], but with doubles that are
+    /// written as integers normalized to have a decimal point.
+    ///
+    /// For example, this would be `1.0` for a `d` key with `1` as the default.
+    pub fn normalized_default_value(&self) -> Cow<'_, str> {
+        let default_value = self.default_value();
+
+        let is_double =
+            matches!(self.signature(), Some(KeySignature::Type(ref type_)) if type_ == "d");
+        let is_integer = default_value
+            .strip_prefix(['-', '+'])
+            .unwrap_or(default_value)
+            .chars()
+            .all(|c| c.is_ascii_digit());
+
+        if is_double && is_integer && !default_value.is_empty() {
+            Cow::Owned(format!("{}.0", default_value))
+        } else {
+            Cow::Borrowed(default_value)
+        }
+    }
+
     pub fn signature(&self) -> Option<KeySignature> {
+        self.parse_signature().ok()
+    }
+
+    /// Parses the signature of the key from its `type`, `enum` and `flags`
+    /// attributes, returning an error message if they are missing or inconsistent.
+    ///
+    /// A `type` can be specified along with an `enum` or `flags`, as long as it
+    /// matches their underlying type, which is `s` and `as` respectively.
+    pub fn parse_signature(&self) -> Result<KeySignature, String> {
         match (&self.type_, &self.enum_id, &self.flag_id) {
-            (Some(type_name), None, None) => Some(KeySignature::Type(type_name.to_string())),
-            (None, Some(enum_id), None) => Some(KeySignature::Enum(enum_id.to_string())),
-            (None, None, Some(flag_id)) => Some(KeySignature::Flag(flag_id.to_string())),
-            _ => None,
+            (Some(type_name), None, None) => Ok(KeySignature::Type(type_name.to_string())),
+            (None, Some(enum_id), None) => Ok(KeySignature::Enum(enum_id.to_string())),
+            (None, None, Some(flag_id)) => Ok(KeySignature::Flag(flag_id.to_string())),
+            (Some(type_name), Some(enum_id), None) => {
+                if type_name == "s" {
+                    Ok(KeySignature::Enum(enum_id.to_string()))
+                } else {
+                    Err(format!(
+                        "key `{}` has type `{}`, but enum `{}` is stored as type `s`; GSettings always stores enums by their nicks, so either remove `type`, or remove `enum` to store a plain `{}`",
+                        self.name, type_name, enum_id, type_name
+                    ))
+                }
+            }
+            (Some(type_name), None, Some(flag_id)) => {
+                if type_name == "as" {
+                    Ok(KeySignature::Flag(flag_id.to_string()))
+                } else {
+                    Err(format!(
+                        "key `{}` has type `{}`, but flags `{}` is stored as type `as`; GSettings always stores flags as arrays of their nicks, so either remove `type`, or remove `flags` to store a plain `{}`",
+                        self.name, type_name, flag_id, type_name
+                    ))
+                }
+            }
+            (None, None, None) => Err(format!(
+                "expected one of `type`, `enum` or `flags` specified attribute on key `{}` in the schema",
+                self.name
+            )),
+            (_, Some(_), Some(_)) => Err(format!(
+                "key `{}` cannot have both `enum` and `flags` specified in the schema",
+                self.name
+            )),
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct Choice {
     #[serde(rename = "@value")]
     pub value: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct Choices {
     #[serde(rename = "choice")]
     pub choices: Vec<Choice>,
 }
 
-#[derive(Debug, Deserialize)]
+/// An alternative nick of a key with an enum or choices, which is accepted as
+/// its target nick
+#[derive(Clone, Debug, Deserialize)]
+pub struct Alias {
+    #[serde(rename = "@value")]
+    pub value: String,
+    #[serde(rename = "@target")]
+    pub target: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Aliases {
+    #[serde(rename = "alias", default)]
+    pub aliases: Vec<Alias>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
 pub struct Range {
     #[serde(rename = "@max")]
     pub max: Option<String>,
     #[serde(rename = "@min")]
     pub min: Option<String>,
+    /// Not part of the GSettings schema format, only parsed to reject
+    /// `<range type="enum">` and `<range type="flags">`
+    #[serde(rename = "@type")]
+    pub type_: Option<String>,
+    /// The id the rejected range refers to, only parsed to suggest the
+    /// `enum` or `flags` attribute instead
+    #[serde(rename = "@id")]
+    pub id: Option<String>,
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Result<String, String> {
+    let chunks = bytes.chunks_exact(2);
+    if !chunks.remainder().is_empty() {
+        return Err("invalid UTF-16: odd number of bytes".to_string());
+    }
+
+    let units = chunks.map(|chunk| from_bytes([chunk[0], chunk[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map_err(|err| format!("invalid UTF-16: {}", err))
 }
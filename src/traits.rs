@@ -0,0 +1,121 @@
+use quote::{format_ident, quote};
+
+use std::collections::HashSet;
+
+use crate::generators::{
+    check_method_names, Crates, KeyGenerator, StructInfo, SETTINGS_EXT_METHOD_NAMES,
+};
+
+/// The trait with the getters, setters, and resets of the keys, which is
+/// implemented by the struct and can be implemented by mocks
+#[derive(Default)]
+pub struct AccessorTrait<'k> {
+    declarations: proc_macro2::TokenStream,
+    implementations: proc_macro2::TokenStream,
+    method_names: Vec<(&'k str, Vec<String>)>,
+}
+
+impl<'k> AccessorTrait<'k> {
+    /// Adds the methods of the key named `key_name`
+    pub fn push(&mut self, key_name: &'k str, generator: &KeyGenerator<'_>) {
+        let (declarations, implementations) = generator.accessor_trait_items();
+        self.declarations.extend(declarations.tokens);
+        self.implementations.extend(implementations);
+        self.method_names.push((key_name, declarations.names));
+    }
+
+    pub fn token_stream(&self, info: &StructInfo<'_>) -> proc_macro2::TokenStream {
+        let StructInfo {
+            ident: struct_ident,
+            vis: struct_vis,
+            ..
+        } = info;
+        let Self {
+            declarations,
+            implementations,
+            ..
+        } = self;
+
+        let trait_ident = format_ident!("{}Ext", struct_ident);
+
+        let trait_docs = format!(
+            "The getters, setters, and resets of [`{}`], which can be implemented by a mock in tests.",
+            struct_ident
+        );
+        quote! {
+            #[doc = #trait_docs]
+            #struct_vis trait #trait_ident {
+                #declarations
+            }
+
+            impl #trait_ident for #struct_ident {
+                #implementations
+            }
+        }
+    }
+
+    /// Emits an error for each method that is generated for multiple keys
+    pub fn check_method_names(self, struct_ident: &syn::Ident) {
+        check_method_names(
+            &format!("`{}Ext`", struct_ident),
+            self.method_names,
+            &HashSet::new(),
+        );
+    }
+}
+
+/// The extension trait of `gio::Settings` with the typed methods of the keys
+#[derive(Default)]
+pub struct SettingsExtTrait<'k> {
+    items: proc_macro2::TokenStream,
+    method_names: Vec<(&'k str, Vec<String>)>,
+}
+
+impl<'k> SettingsExtTrait<'k> {
+    /// Adds the methods of the key named `key_name`
+    pub fn push(
+        &mut self,
+        key_name: &'k str,
+        generator: &KeyGenerator<'_>,
+        struct_ident: &syn::Ident,
+    ) {
+        let items = generator.settings_ext_items(struct_ident);
+        self.items.extend(items.tokens);
+        self.method_names.push((key_name, items.names));
+    }
+
+    pub fn token_stream(&self, info: &StructInfo<'_>) -> proc_macro2::TokenStream {
+        let StructInfo {
+            ident: struct_ident,
+            vis: struct_vis,
+            crates: Crates { gio, glib },
+            ..
+        } = info;
+        let items = &self.items;
+
+        let trait_ident = format_ident!("{}Ext", struct_ident);
+
+        let trait_docs = format!(
+            "The typed methods of [`{}`], available on any `gio::Settings` of its schema.",
+            struct_ident
+        );
+        quote! {
+            #[doc = #trait_docs]
+            #struct_vis trait #trait_ident: #glib::object::IsA<#gio::Settings> {
+                #items
+            }
+
+            impl<O: #glib::object::IsA<#gio::Settings>> #trait_ident for O {}
+        }
+    }
+
+    /// Emits an error for each method that is generated for multiple keys, or
+    /// that would be ambiguous with a method of `SettingsExt`
+    pub fn check_method_names(self, struct_ident: &syn::Ident) {
+        check_method_names(
+            &format!("`{}Ext`", struct_ident),
+            self.method_names,
+            &SETTINGS_EXT_METHOD_NAMES.iter().copied().collect(),
+        );
+    }
+}
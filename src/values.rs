@@ -0,0 +1,112 @@
+use quote::{format_ident, quote};
+
+use crate::generators::{Crates, KeyGenerator, StructInfo};
+
+/// The values struct, with a field for each key, which is generated along with
+/// `values` and `set_all`
+#[derive(Default)]
+pub struct ValuesStruct {
+    fields: proc_macro2::TokenStream,
+    inits: proc_macro2::TokenStream,
+    writes: proc_macro2::TokenStream,
+}
+
+impl ValuesStruct {
+    /// Names of the methods generated on the struct
+    pub const METHOD_NAMES: &'static [&'static str] = &["values", "set_all"];
+
+    /// Adds the field of the key of `generator`
+    pub fn push(&mut self, generator: &KeyGenerator<'_>) {
+        let (field, init, write) = generator.values_struct_items();
+        self.fields.extend(field);
+        self.inits.extend(init);
+        self.writes.extend(write);
+    }
+
+    pub fn token_stream(&self, info: &StructInfo<'_>) -> proc_macro2::TokenStream {
+        let StructInfo {
+            ident: struct_ident,
+            vis: struct_vis,
+            field,
+            crates: Crates { gio, glib },
+        } = info;
+        let Self {
+            fields,
+            inits,
+            writes,
+        } = self;
+        let settings = field.access();
+
+        let values_ident = format_ident!("{}Values", struct_ident);
+        let values_docs = format!(
+            "A snapshot of the values of the keys of [`{}`], with a field for each key.",
+            struct_ident
+        );
+        quote! {
+            #[doc = #values_docs]
+            #[derive(Clone, Debug, PartialEq)]
+            #struct_vis struct #values_ident {
+                #fields
+            }
+
+            impl #struct_ident {
+                /// Returns the current values of all keys.
+                pub fn values(&self) -> #values_ident {
+                    #values_ident {
+                        #inits
+                    }
+                }
+
+                /// Writes the keys whose current values differ from `values`,
+                /// applying the changes at once.
+                ///
+                /// Keys whose value in `values` is their default are reset
+                /// instead, so they keep following the default of the schema.
+                /// The keys that are not writable are skipped, and returned in
+                /// the error after writing the rest. If the settings are already
+                /// in delay-apply mode, the changes are left unapplied instead.
+                pub fn set_all(&self, values: &#values_ident) -> std::result::Result<(), #glib::BoolError> {
+                    let is_delayed = #gio::prelude::SettingsExt::is_delay_apply(&#settings);
+                    // Writing to a delayed copy applies the changes at once, without
+                    // putting these settings in delay-apply mode, which can't be undone
+                    let settings = if is_delayed {
+                        #gio::Settings::clone(&#settings)
+                    } else {
+                        let settings = #glib::Object::builder::<#gio::Settings>()
+                            .property(
+                                "settings-schema",
+                                #glib::prelude::ObjectExt::property::<#gio::SettingsSchema>(&#settings, "settings-schema"),
+                            )
+                            .property(
+                                "path",
+                                #glib::prelude::ObjectExt::property::<String>(&#settings, "path"),
+                            )
+                            .property(
+                                "backend",
+                                #glib::prelude::ObjectExt::property::<#gio::SettingsBackend>(&#settings, "backend"),
+                            )
+                            .build();
+                        #gio::prelude::SettingsExt::delay(&settings);
+                        settings
+                    };
+
+                    let mut unwritable_keys = Vec::<&str>::new();
+                    #writes
+
+                    if !is_delayed {
+                        #gio::prelude::SettingsExt::apply(&settings);
+                    }
+
+                    if unwritable_keys.is_empty() {
+                        Ok(())
+                    } else {
+                        Err(#glib::bool_error!(
+                            "keys are not writable: {}",
+                            unwritable_keys.join(", ")
+                        ))
+                    }
+                }
+            }
+        }
+    }
+}
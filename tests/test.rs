@@ -5,12 +5,14 @@ use gio::{
 use gsettings_macro::gen_settings;
 
 use std::{
-    cell::Cell,
+    cell::{Cell, RefCell},
+    collections::HashMap,
     env,
     path::{Path, PathBuf},
     process::Command,
     rc::Rc,
     sync::Once,
+    time::Duration,
 };
 
 static INIT: Once = Once::new();
@@ -42,6 +44,72 @@ fn setup_schema() {
     });
 }
 
+// The settings of the main schema, shared by the tests that need no options
+#[gen_settings(
+    file = "./tests/io.github.seadve.test.gschema.xml",
+    id = "io.github.seadve.test"
+)]
+#[gen_settings_skip(signature = "(ss)")]
+#[gen_settings_skip(signature = "ay")]
+pub struct Settings;
+
+/// Sets up the schema and creates the settings of the main schema
+fn test_settings() -> Settings {
+    setup_schema();
+    Settings::new()
+}
+
+#[test]
+#[serial_test::serial]
+fn ui() {
+    setup_schema();
+
+    // The installed schemas are looked up when the test cases are compiled,
+    // along with the enum files next to them, skipping the unparseable files
+    let schema_dir = env::temp_dir().join(format!("gsettings-macro-{}", std::process::id()));
+    std::fs::create_dir_all(&schema_dir).unwrap();
+    std::fs::write(
+        schema_dir.join("io.github.seadve.test.installed.gschema.xml"),
+        r#"
+            <schemalist>
+                <schema path="/io/github/seadve/test/installed/" id="io.github.seadve.test.installed">
+                    <key name="layout" enum="io.github.seadve.test.installed.Layout">
+                        <default>'list'</default>
+                    </key>
+                </schema>
+            </schemalist>
+        "#,
+    )
+    .unwrap();
+    std::fs::write(
+        schema_dir.join("io.github.seadve.test.installed.enums.xml"),
+        r#"
+            <schemalist>
+                <enum id="io.github.seadve.test.installed.Layout">
+                    <value nick="grid" value="0"/>
+                    <value nick="list" value="1"/>
+                </enum>
+            </schemalist>
+        "#,
+    )
+    .unwrap();
+    std::fs::write(schema_dir.join("broken.gschema.xml"), "<schemalist>").unwrap();
+    std::fs::write(schema_dir.join("broken.enums.xml"), "<schemalist>").unwrap();
+
+    let old_schema_dir = env::var_os("GSETTINGS_SCHEMA_DIR");
+    env::set_var("GSETTINGS_SCHEMA_DIR", &schema_dir);
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/ui/*.rs");
+        t.pass("tests/ui/pass/*.rs");
+    }
+    match old_schema_dir {
+        Some(old_schema_dir) => env::set_var("GSETTINGS_SCHEMA_DIR", old_schema_dir),
+        None => env::remove_var("GSETTINGS_SCHEMA_DIR"),
+    }
+    std::fs::remove_dir_all(&schema_dir).unwrap();
+}
+
 #[test]
 #[serial_test::serial]
 fn setter_and_getter_func() {
@@ -75,6 +143,9 @@ fn setter_and_getter_func() {
     settings.set_window_height_64(30_000);
     assert_eq!(settings.window_height_64(), 30_000);
 
+    settings.set_file_descriptor(glib::variant::Handle(3));
+    assert_eq!(settings.file_descriptor().0, 3);
+
     settings.set_volume(1.0);
     assert_eq!(settings.volume(), 1.0);
 
@@ -100,6 +171,7 @@ fn create_action_func() {
         ret_type = "(String, String)"
     )]
     #[gen_settings_define(key_name = "cache-dir", arg_type = "&Path", ret_type = "PathBuf")]
+    #[gen_settings_skip(key_name = "data-dir")]
     pub struct SomeAppSettings;
 
     let settings = SomeAppSettings::new("io.github.seadve.test");
@@ -131,6 +203,274 @@ fn create_action_func() {
     assert_eq!(settings.create_dimensions_action().name(), "dimensions");
     assert_eq!(settings.create_string_tuple_action().name(), "string-tuple");
     assert_eq!(settings.create_cache_dir_action().name(), "cache-dir");
+
+    assert_eq!(SomeAppSettings::IS_MAXIMIZED_ACTION_NAME, "is-maximized");
+    assert_eq!(
+        SomeAppSettings::WINDOW_WIDTH_64_ACTION_NAME,
+        "window-width-64"
+    );
+    assert_eq!(
+        settings.create_preferred_audio_source_action().name(),
+        SomeAppSettings::PREFERRED_AUDIO_SOURCE_ACTION_NAME
+    );
+}
+
+#[test]
+#[serial_test::serial]
+fn functions_module_defined_in_macro() {
+    setup_schema();
+
+    mod settings {
+        use gio::glib;
+        use gsettings_macro::gen_settings;
+
+        #[gen_settings(
+            file = "./tests/io.github.seadve.test.gschema.xml",
+            id = "io.github.seadve.test",
+            functions_module = "app_settings"
+        )]
+        #[gen_settings_skip(signature = "(ss)")]
+        #[gen_settings_skip(signature = "ay")]
+        pub struct Settings;
+    }
+
+    use settings::{app_settings, AlertSound};
+
+    let settings = gio::Settings::new("io.github.seadve.test");
+    assert_eq!(app_settings::window_width(&settings), 600);
+
+    let changed = Rc::new(Cell::new(false));
+    let changed_clone = Rc::clone(&changed);
+    app_settings::connect_window_width_changed(&settings, move |_| changed_clone.set(true));
+
+    app_settings::set_window_width(&settings, 100);
+    assert_eq!(app_settings::window_width(&settings), 100);
+    assert!(changed.get());
+
+    app_settings::try_set_alert_sound(&settings, AlertSound::Glass).unwrap();
+    assert_eq!(app_settings::alert_sound(&settings), AlertSound::Glass);
+
+    app_settings::reset_window_width(&settings);
+    app_settings::reset_alert_sound(&settings);
+    assert_eq!(app_settings::window_width(&settings), 600);
+}
+
+#[test]
+#[serial_test::serial]
+fn skip_kind_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    #[gen_settings_skip(kind = "array")]
+    #[gen_settings_skip(kind = "dict")]
+    #[gen_settings_skip(kind = "tuple")]
+    // Defines by key name take precedence over skips by kind
+    #[gen_settings_define(
+        key_name = "invalid-words",
+        arg_type = "&[&str]",
+        ret_type = "Vec<String>"
+    )]
+    pub struct Settings;
+
+    // These would conflict with the generated methods if they were not skipped
+    impl Settings {
+        fn dimensions(&self) -> (i32, i32) {
+            self.get("dimensions")
+        }
+
+        fn extra_options(&self) -> HashMap<String, glib::Variant> {
+            self.get("extra-options")
+        }
+
+        fn listen_ports(&self) -> Vec<u16> {
+            self.get("listen-ports")
+        }
+    }
+
+    let settings = Settings::new();
+    settings.reset("dimensions");
+    assert_eq!(settings.dimensions(), (10, 10));
+    assert_eq!(settings.extra_options().len(), 2);
+    assert_eq!(settings.listen_ports(), vec![8080, 8081]);
+
+    // Flags are not arrays
+    assert_eq!(settings.space_style(), SpaceStyle::empty());
+
+    settings.set_invalid_words(&["teh"]);
+    assert_eq!(settings.invalid_words(), vec!["teh"]);
+    settings.reset_invalid_words();
+}
+
+#[test]
+#[serial_test::serial]
+fn bind_to_action_func() {
+    let settings = test_settings();
+
+    let action = gio::SimpleAction::new_stateful("maximized", None, &true.to_variant());
+    settings.bind_is_maximized_to_action(&action);
+    assert_eq!(action.state(), Some(false.to_variant()));
+
+    settings.set_is_maximized(true);
+    assert_eq!(action.state(), Some(true.to_variant()));
+
+    action.activate(None);
+    assert!(!settings.is_maximized());
+    assert_eq!(action.state(), Some(false.to_variant()));
+
+    // Disconnecting both handlers removes the binding
+    let unbound_action = gio::SimpleAction::new_stateful("unbound", None, &true.to_variant());
+    let (changed_handler_id, change_state_handler_id) =
+        settings.bind_is_maximized_to_action(&unbound_action);
+    settings.disconnect(changed_handler_id);
+    unbound_action.disconnect(change_state_handler_id);
+    settings.set_is_maximized(true);
+    assert_eq!(unbound_action.state(), Some(false.to_variant()));
+    unbound_action.activate(None);
+    assert!(settings.is_maximized());
+
+    let action = gio::SimpleAction::new_stateful(
+        "alert-sound",
+        Some(VariantTy::STRING),
+        &"drip".to_variant(),
+    );
+    settings.bind_alert_sound_to_action(&action);
+    assert_eq!(action.state(), Some("bark".to_variant()));
+
+    action.change_state(&"glass".to_variant());
+    assert_eq!(settings.alert_sound(), AlertSound::Glass);
+    assert_eq!(action.state(), Some("glass".to_variant()));
+
+    // Invalid values are not written, and the state is kept
+    action.change_state(&"invalid".to_variant());
+    assert_eq!(settings.alert_sound(), AlertSound::Glass);
+    assert_eq!(action.state(), Some("glass".to_variant()));
+
+    settings.reset_is_maximized();
+    settings.reset_alert_sound();
+}
+
+#[test]
+#[serial_test::serial]
+fn enum_aliases() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    assert_eq!("woof".parse::<AlertSound>().unwrap(), AlertSound::Bark);
+    assert_eq!(
+        AlertSound::from_variant(&"woof".to_variant()),
+        Some(AlertSound::Bark)
+    );
+    assert_eq!(AlertSound::Bark.to_variant().str(), Some("bark"));
+
+    assert_eq!(
+        "mic".parse::<PreferredAudioSource>().unwrap(),
+        PreferredAudioSource::Microphone
+    );
+    assert_eq!(
+        PreferredAudioSource::from_variant(&"mic".to_variant()),
+        Some(PreferredAudioSource::Microphone)
+    );
+    assert_eq!(PreferredAudioSource::VARIANTS.len(), 2);
+
+    let settings = Settings::new();
+    settings.set_alert_sound_from_str("woof").unwrap();
+    assert_eq!(settings.alert_sound(), AlertSound::Bark);
+    assert_eq!(settings.alert_sound_nick(), "bark");
+
+    settings.reset_alert_sound();
+}
+
+#[test]
+#[serial_test::serial]
+fn signature_consts() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    assert_eq!(Settings::SIGNATURE_WINDOW_WIDTH, "i");
+    assert_eq!(Settings::SIGNATURE_WINDOW_STATE, "(sa{sv})");
+    assert_eq!(Settings::SIGNATURE_PREFERRED_AUDIO_SOURCE, "s");
+    assert_eq!(Settings::SIGNATURE_ALERT_SOUND, "s");
+    assert_eq!(Settings::SIGNATURE_SPACE_STYLE, "as");
+
+    let settings = Settings::new();
+    assert_eq!(
+        settings.value("window-state").type_().as_str(),
+        Settings::SIGNATURE_WINDOW_STATE
+    );
+}
+
+#[test]
+#[serial_test::serial]
+fn create_enum_action_func() {
+    let settings = test_settings();
+    let action = settings.create_alert_sound_action();
+    assert_eq!(action.state(), Some("bark".to_variant()));
+
+    // Menu items target the action by nick, for example, `app.alert-sound::glass`
+    let (action_name, target) = gio::Action::parse_detailed_name("alert-sound::glass").unwrap();
+    assert_eq!(action_name, Settings::ALERT_SOUND_ACTION_NAME);
+    action.activate(target.as_ref());
+    assert_eq!(settings.alert_sound(), AlertSound::Glass);
+    assert_eq!(action.state(), Some("glass".to_variant()));
+
+    settings.set_alert_sound(AlertSound::Drip);
+    assert_eq!(action.state(), Some("drip".to_variant()));
+
+    settings.reset_alert_sound();
+}
+
+#[test]
+#[serial_test::serial]
+fn create_property_action_func() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct SomeAppSettings;
+
+    let settings = SomeAppSettings::new("io.github.seadve.test");
+    let object = gio::SimpleAction::new("some-action", None);
+    assert!(object.is_enabled());
+
+    let action = settings.create_is_maximized_property_action(&object, "enabled");
+    assert_eq!(action.name(), SomeAppSettings::IS_MAXIMIZED_ACTION_NAME);
+    assert_eq!(action.state(), Some(false.to_variant()));
+    assert!(!object.is_enabled());
+
+    action.activate(None);
+    assert!(object.is_enabled());
+    assert!(settings.is_maximized());
+
+    settings.set_is_maximized(false);
+    assert!(!object.is_enabled());
+    assert_eq!(action.state(), Some(false.to_variant()));
+
+    // The binding outlives the action until it is removed
+    drop(action);
+    settings.set_is_maximized(true);
+    assert!(object.is_enabled());
+
+    gio::Settings::unbind(&object, "enabled");
+    settings.set_is_maximized(false);
+    assert!(object.is_enabled());
 }
 
 #[test]
@@ -153,6 +493,101 @@ fn reset_func() {
     assert_eq!(settings.alert_sound(), AlertSound::Bark);
 }
 
+#[test]
+#[serial_test::serial]
+fn nick_func() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct SomeAppSettings;
+
+    let settings = SomeAppSettings::new("io.github.seadve.test");
+    assert_eq!(settings.alert_sound_nick(), "bark");
+    assert_eq!(settings.preferred_audio_source_nick(), "microphone");
+
+    settings.set_alert_sound(AlertSound::Glass);
+    assert_eq!(settings.alert_sound_nick(), "glass");
+
+    settings.set_preferred_audio_source(PreferredAudioSource::DesktopAudio);
+    assert_eq!(settings.preferred_audio_source_nick(), "desktop-audio");
+
+    settings.reset_alert_sound();
+    settings.reset_preferred_audio_source();
+}
+
+#[test]
+#[serial_test::serial]
+fn set_from_str_func() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    assert_eq!("drip".parse::<AlertSound>().unwrap(), AlertSound::Drip);
+    assert!("Drip".parse::<AlertSound>().is_err());
+
+    let settings = Settings::new();
+    settings.set_alert_sound_from_str("glass").unwrap();
+    assert_eq!(settings.alert_sound(), AlertSound::Glass);
+
+    assert!(settings.set_alert_sound_from_str("meow").is_err());
+    assert_eq!(settings.alert_sound(), AlertSound::Glass);
+
+    settings.reset_alert_sound();
+}
+
+#[test]
+fn enum_variants() {
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct SomeAppSettings;
+
+    assert_eq!(
+        AlertSound::VARIANTS,
+        &[AlertSound::Bark, AlertSound::Glass, AlertSound::Drip]
+    );
+    assert_eq!(AlertSound::default(), AlertSound::Bark);
+    assert_eq!(
+        PreferredAudioSource::default(),
+        PreferredAudioSource::Microphone
+    );
+    assert_eq!(
+        PreferredAudioSource::VARIANTS,
+        &[
+            PreferredAudioSource::Microphone,
+            PreferredAudioSource::DesktopAudio
+        ]
+    );
+}
+
+#[test]
+#[serial_test::serial]
+fn struct_attributes() {
+    setup_schema();
+
+    /// Settings with user-specified attributes
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[derive(PartialOrd, Ord)]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    let other = settings.clone();
+    assert_eq!(settings.cmp(&other), std::cmp::Ordering::Equal);
+}
+
 #[test]
 #[serial_test::serial]
 fn default_value_func() {
@@ -163,6 +598,7 @@ fn default_value_func() {
         ret_type = "(String, String)"
     )]
     #[gen_settings_define(key_name = "cache-dir", arg_type = "&Path", ret_type = "PathBuf")]
+    #[gen_settings_skip(key_name = "data-dir")]
     pub struct Settings;
 
     let settings = Settings::new("io.github.seadve.test");
@@ -173,6 +609,7 @@ fn default_value_func() {
     assert_eq!(settings.window_height_default_value(), 400);
     assert_eq!(settings.window_width_64_default_value(), 600);
     assert_eq!(settings.window_height_64_default_value(), 400);
+    assert_eq!(settings.file_descriptor_default_value().0, 0);
     assert_eq!(settings.volume_default_value(), 6.3);
     assert_eq!(
         settings.preferred_audio_source_default_value(),
@@ -191,6 +628,32 @@ fn default_value_func() {
     assert_eq!(settings.space_style_default_value(), SpaceStyle::empty());
 }
 
+#[test]
+#[serial_test::serial]
+fn is_default_func() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new("io.github.seadve.test");
+    assert!(settings.window_width_is_default());
+
+    settings.set_window_width(1);
+    assert!(!settings.window_width_is_default());
+
+    settings.set_window_width(600);
+    assert!(settings.window_width_is_default());
+
+    settings.set_alert_sound(AlertSound::Glass);
+    assert!(!settings.alert_sound_is_default());
+
+    settings.reset_alert_sound();
+    assert!(settings.alert_sound_is_default());
+}
+
 #[test]
 #[serial_test::serial]
 fn other_func() {
@@ -241,28 +704,199 @@ fn other_func() {
 
 #[test]
 #[serial_test::serial]
-fn custom_define_signature() {
+fn connect_any_changed_func() {
     setup_schema();
 
     #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
-    #[gen_settings_define(
-        signature = "(ss)",
-        arg_type = "(&str, &str)",
-        ret_type = "(String, String)"
-    )]
+    #[gen_settings_skip(signature = "(ss)")]
     #[gen_settings_skip(signature = "ay")]
     pub struct Settings;
 
     let settings = Settings::new("io.github.seadve.test");
 
-    settings.set_string_tuple(("hi", "hi2"));
-    assert_eq!(settings.string_tuple(), ("hi".into(), "hi2".into()));
+    let changed_keys = Rc::new(RefCell::new(Vec::new()));
 
-    settings.set_two_strings(("a string", "another string"));
-    assert_eq!(
-        settings.two_strings(),
-        ("a string".into(), "another string".into())
-    );
+    let changed_keys_clone = Rc::clone(&changed_keys);
+    let handler_id = settings.connect_any_changed(move |_, key| {
+        changed_keys_clone.borrow_mut().push(key.to_string());
+    });
+
+    settings.set_window_width(10);
+    settings.set_theme("dark");
+    assert_eq!(*changed_keys.borrow(), ["window-width", "theme"]);
+
+    settings.disconnect(handler_id);
+
+    settings.set_volume(2.0);
+    assert_eq!(changed_keys.borrow().len(), 2);
+
+    settings.reset_window_width();
+    settings.reset_theme();
+    settings.reset_volume();
+}
+
+#[test]
+#[serial_test::serial]
+fn connect_applied_func() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new("io.github.seadve.test");
+    settings.delay();
+
+    let applied_keys = Rc::new(RefCell::new(Vec::new()));
+
+    let applied_keys_clone = Rc::clone(&applied_keys);
+    let handler_ids = settings.connect_applied(move |_, keys| {
+        applied_keys_clone.borrow_mut().push(keys.to_vec());
+    });
+
+    settings.set_window_width(10);
+    settings.set_theme("dark");
+    settings.set_window_width(20);
+    assert!(applied_keys.borrow().is_empty());
+
+    settings.apply();
+    assert_eq!(*applied_keys.borrow(), [["window-width", "theme"]]);
+
+    settings.set_volume(2.0);
+    settings.revert();
+    assert_eq!(applied_keys.borrow().len(), 1);
+
+    settings.set_volume(3.0);
+    settings.apply();
+    assert_eq!(applied_keys.borrow().len(), 2);
+    assert_eq!(applied_keys.borrow()[1], ["volume"]);
+
+    for handler_id in handler_ids {
+        settings.disconnect(handler_id);
+    }
+
+    settings.set_volume(4.0);
+    settings.apply();
+    assert_eq!(applied_keys.borrow().len(), 2);
+
+    settings.reset_window_width();
+    settings.reset_theme();
+    settings.reset_volume();
+    settings.apply();
+}
+
+#[test]
+#[serial_test::serial]
+fn iter_keys_func() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new("io.github.seadve.test");
+    // The keys are in the order they are defined in the schema
+    let schema_source =
+        std::fs::read_to_string("./tests/io.github.seadve.test.gschema.xml").unwrap();
+    let key_names = schema_source
+        .split("<key name=\"")
+        .skip(1)
+        .map(|rest| rest.split('"').next().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(Settings::KEYS, key_names);
+
+    let mut installed_key_names = settings.settings_schema().unwrap().list_keys();
+    installed_key_names.sort();
+    let mut sorted_key_names = key_names.clone();
+    sorted_key_names.sort_unstable();
+    assert_eq!(installed_key_names, sorted_key_names);
+
+    settings.set_window_width(10);
+
+    let keys = settings.iter_keys().collect::<Vec<_>>();
+    assert_eq!(
+        keys.iter().map(|(key, _)| *key).collect::<Vec<_>>(),
+        Settings::KEYS
+    );
+    assert!(keys.contains(&("window-width", 10.to_variant())));
+    assert!(keys.contains(&("theme", settings.theme().to_variant())));
+    assert!(keys.contains(&("alert-sound", "bark".to_variant())));
+
+    settings.reset_window_width();
+}
+
+#[test]
+#[serial_test::serial]
+fn nested_signature() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new("io.github.seadve.test");
+
+    let (name, properties) = settings.window_state_default_value();
+    assert_eq!(name, "main");
+    assert_eq!(properties.len(), 1);
+    assert_eq!(properties["maximized"].get::<bool>(), Some(false));
+
+    let properties = HashMap::from([
+        ("maximized".to_string(), true.to_variant()),
+        ("width".to_string(), 600.to_variant()),
+        ("title".to_string(), "Some title".to_variant()),
+    ]);
+    settings.set_window_state(("secondary", &properties));
+
+    let (name, stored_properties) = settings.window_state();
+    assert_eq!(name, "secondary");
+    assert_eq!(stored_properties, properties);
+    assert_eq!(stored_properties["width"].get::<i32>(), Some(600));
+
+    settings.reset_window_state();
+}
+
+#[test]
+#[serial_test::serial]
+fn children_func() {
+    let settings = test_settings();
+    assert_eq!(settings.children(), vec!["default-profile".to_string()]);
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.relocatable.gschema.xml",
+        id = "io.github.seadve.test.relocatable",
+        path = "/io/github/seadve/test/profiles/children/"
+    )]
+    pub struct ProfileSettings;
+
+    assert!(ProfileSettings::new().children().is_empty());
+}
+
+#[test]
+#[serial_test::serial]
+fn custom_define_signature() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_define(
+        signature = "(ss)",
+        arg_type = "(&str, &str)",
+        ret_type = "(String, String)"
+    )]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new("io.github.seadve.test");
+
+    settings.set_string_tuple(("hi", "hi2"));
+    assert_eq!(settings.string_tuple(), ("hi".into(), "hi2".into()));
+
+    settings.set_two_strings(("a string", "another string"));
+    assert_eq!(
+        settings.two_strings(),
+        ("a string".into(), "another string".into())
+    );
 }
 
 #[test]
@@ -272,6 +906,7 @@ fn custom_define_key_name() {
 
     #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
     #[gen_settings_define(key_name = "cache-dir", arg_type = "&Path", ret_type = "PathBuf")]
+    #[gen_settings_skip(key_name = "data-dir")]
     #[gen_settings_skip(signature = "(ss)")]
     pub struct Settings;
 
@@ -280,9 +915,56 @@ fn custom_define_key_name() {
     assert_eq!(settings.cache_dir(), PathBuf::from("/some_dir"));
 }
 
+#[test]
+#[serial_test::serial]
+fn custom_define_signature_choices() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_define(signature = "s", arg_type = "String", ret_type = "String")]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new("io.github.seadve.test");
+
+    settings.set_theme("dark".to_string());
+    assert_eq!(settings.theme(), "dark");
+
+    settings.set_preferred_audio_source(PreferredAudioSource::DesktopAudio);
+    assert_eq!(
+        settings.preferred_audio_source(),
+        PreferredAudioSource::DesktopAudio
+    );
+
+    settings.reset_theme();
+    settings.reset_preferred_audio_source();
+}
+
+#[test]
+#[serial_test::serial]
+fn custom_define_signature_forced() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_define(signature = "s", arg_type = "&str", ret_type = "String", force = true)]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new("io.github.seadve.test");
+
+    settings.set_preferred_audio_source("desktop-audio");
+    assert_eq!(settings.preferred_audio_source(), "desktop-audio");
+
+    settings.reset_preferred_audio_source();
+}
+
 #[test]
 #[serial_test::serial]
 fn overlapping_define() {
+    use std::ffi::{OsStr, OsString};
+
     setup_schema();
 
     #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
@@ -294,6 +976,13 @@ fn overlapping_define() {
     let settings = Settings::new("io.github.seadve.test");
     settings.set_cache_dir(Path::new("/some_dir"));
     assert_eq!(settings.cache_dir(), PathBuf::from("/some_dir"));
+
+    // The signature define still applies to the other keys
+    settings.set_data_dir(OsStr::new("/some_other_dir"));
+    assert_eq!(settings.data_dir(), OsString::from("/some_other_dir"));
+
+    settings.reset_cache_dir();
+    settings.reset_data_dir();
 }
 
 #[test]
@@ -339,17 +1028,7 @@ fn string_choice_enum() {
 #[test]
 #[serial_test::serial]
 fn enumeration() {
-    setup_schema();
-
-    #[gen_settings(
-        file = "./tests/io.github.seadve.test.gschema.xml",
-        id = "io.github.seadve.test"
-    )]
-    #[gen_settings_skip(signature = "(ss)")]
-    #[gen_settings_skip(signature = "ay")]
-    pub struct Settings;
-
-    let settings = Settings::new();
+    let settings = test_settings();
 
     assert_eq!(settings.alert_sound(), AlertSound::Bark);
 
@@ -381,17 +1060,7 @@ fn enumeration_value() {
 #[test]
 #[serial_test::serial]
 fn bitflag() {
-    setup_schema();
-
-    #[gen_settings(
-        file = "./tests/io.github.seadve.test.gschema.xml",
-        id = "io.github.seadve.test"
-    )]
-    #[gen_settings_skip(signature = "(ss)")]
-    #[gen_settings_skip(signature = "ay")]
-    pub struct Settings;
-
-    let settings = Settings::new();
+    let settings = test_settings();
 
     assert_eq!(SpaceStyle::static_variant_type(), VariantTy::STRING_ARRAY);
 
@@ -412,6 +1081,48 @@ fn bitflag() {
     assert_eq!(settings.space_style(), SpaceStyle::all());
 }
 
+#[test]
+#[serial_test::serial]
+fn bitflag_actions() {
+    let settings = test_settings();
+    settings.set_space_style(SpaceStyle::BEFORE_COMMA);
+
+    let group = settings.create_space_style_flag_actions();
+    let mut action_names = group.list_actions();
+    action_names.sort();
+    assert_eq!(
+        action_names,
+        ["before-colon", "before-comma", "before-semicolon"]
+    );
+    assert_eq!(group.action_state("before-comma"), Some(true.to_variant()));
+    assert_eq!(group.action_state("before-colon"), Some(false.to_variant()));
+
+    group.activate_action("before-colon", None);
+    assert_eq!(
+        settings.space_style(),
+        SpaceStyle::BEFORE_COLON | SpaceStyle::BEFORE_COMMA
+    );
+    assert_eq!(group.action_state("before-colon"), Some(true.to_variant()));
+
+    group.change_action_state("before-comma", &false.to_variant());
+    assert_eq!(settings.space_style(), SpaceStyle::BEFORE_COLON);
+
+    settings.set_space_style(SpaceStyle::BEFORE_SEMICOLON);
+    assert_eq!(
+        group.action_state("before-semicolon"),
+        Some(true.to_variant())
+    );
+    assert_eq!(group.action_state("before-colon"), Some(false.to_variant()));
+
+    // The actions stop following the key once the group is finalized
+    let action = group.lookup_action("before-colon").unwrap();
+    drop(group);
+    settings.set_space_style(SpaceStyle::BEFORE_COLON);
+    assert_eq!(action.state(), Some(false.to_variant()));
+
+    settings.reset_space_style();
+}
+
 #[test]
 #[serial_test::serial]
 fn bitflag_value() {
@@ -475,28 +1186,2253 @@ fn id_defined_in_macro() {
     );
 }
 
+#[test]
+fn mixed_case_key_name() {
+    #[gen_settings(file = "./tests/schemas/io.github.seadve.test.mixed-case.gschema.xml")]
+    pub struct Settings;
+
+    // The schema can't be compiled, so only check the generated method names
+    let _: fn(&Settings) -> String = Settings::http_proxy;
+    let _: fn(&Settings, &str) = Settings::set_http_proxy;
+    let _: fn(&Settings) -> bool = Settings::use_tls;
+    let _: fn(&Settings, bool) = Settings::set_use_tls;
+    let _: fn(&Settings) -> LogLevel = Settings::log_level;
+    let _: fn(&Settings, LogLevel) = Settings::set_log_level;
+    let _: fn(&Settings) -> u32 = Settings::max_cache_size;
+    let _: fn(&Settings, u32) = Settings::set_max_cache_size;
+    assert_eq!(Settings::MAX_CACHE_SIZE_ACTION_NAME, "max.Cache--size");
+}
+
+#[test]
+fn typed_enum_and_flags_key() {
+    #[gen_settings(file = "./tests/schemas/io.github.seadve.test.typed-enum.gschema.xml")]
+    pub struct Settings;
+
+    // The schema can't be compiled, so only check the generated method types
+    let _: fn(&Settings) -> Quality = Settings::quality;
+    let _: fn(&Settings, Quality) = Settings::set_quality;
+    let _: fn(&Settings) -> Channels = Settings::channels;
+    let _: fn(&Settings, Channels) = Settings::set_channels;
+
+    // The stored types are the same as without the explicit `type`
+    assert_eq!(Settings::SIGNATURE_QUALITY, "s");
+    assert_eq!(Settings::SIGNATURE_CHANNELS, "as");
+    assert_eq!(Quality::High.to_variant().type_(), VariantTy::STRING);
+    assert_eq!(Channels::LEFT.to_variant().type_(), VariantTy::STRING_ARRAY);
+}
+
+#[test]
+fn case_colliding_enum_nicks() {
+    // Generating the enum would abort, as `auto` and `Auto` both map to `Auto`
+    #[gen_settings(file = "./tests/schemas/io.github.seadve.test.case-colliding.gschema.xml")]
+    #[gen_settings_define(key_name = "mode", arg_type = "&str", ret_type = "String")]
+    pub struct Settings;
+
+    let _: fn(&Settings) -> String = Settings::mode;
+    let _: fn(&Settings, &str) = Settings::set_mode;
+}
+
+#[test]
+fn describe_defined_in_macro() {
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml", describe = true)]
+    pub struct Settings;
+
+    let descriptors = Settings::describe();
+    assert_eq!(
+        descriptors
+            .iter()
+            .map(|descriptor| descriptor.name)
+            .collect::<Vec<_>>(),
+        Settings::KEYS
+    );
+
+    let volume = descriptors
+        .iter()
+        .find(|descriptor| descriptor.name == "volume")
+        .unwrap();
+    assert_eq!(
+        volume,
+        &SettingsKeyDescriptor {
+            name: "volume",
+            signature: "d",
+            summary: Some("Volume"),
+            description: Some("Recommended maximum is 8"),
+            default_value: 6.3.to_variant(),
+            range: Some((1.0.to_variant(), 10.0.to_variant())),
+        }
+    );
+
+    let alert_sound = descriptors
+        .iter()
+        .find(|descriptor| descriptor.name == "alert-sound")
+        .unwrap();
+    assert_eq!(alert_sound.signature, "s");
+    assert_eq!(alert_sound.default_value, "bark".to_variant());
+    assert_eq!(alert_sound.range, None);
+
+    let file_descriptor = descriptors
+        .iter()
+        .find(|descriptor| descriptor.name == "file-descriptor")
+        .unwrap();
+    assert_eq!(file_descriptor.description, None);
+}
+
 #[test]
 #[serial_test::serial]
-fn private_struct() {
+fn only_defined_in_macro() {
     setup_schema();
 
-    mod inner {
-        use gio::glib;
-        use gsettings_macro::gen_settings;
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        only = ["window-width", "is-maximized"]
+    )]
+    #[gen_settings_define(key_name = "window-width", arg_type = "i32", ret_type = "i32")]
+    pub struct Settings;
 
-        #[gen_settings(
-            file = "./tests/io.github.seadve.test.gschema.xml",
-            id = "io.github.seadve.test"
-        )]
-        #[gen_settings_skip(signature = "(ss)")]
-        #[gen_settings_skip(signature = "ay")]
-        struct Settings;
-    }
+    // Listed keys are kept in the order of the schema
+    assert_eq!(Settings::KEYS, &["is-maximized", "window-width"]);
 
-    // TODO: Use `trybuild` to test if these would cause failed compilation
-    //
-    // use inner::AlertSound;
-    // use inner::PreferredAudioSource;
-    // use inner::Settings;
-    // use inner::SpaceStyle;
+    let settings = Settings::new();
+    settings.set_window_width(640);
+    settings.set_is_maximized(true);
+    assert_eq!(settings.window_width(), 640);
+    assert!(settings.is_maximized());
+
+    settings.reset_all();
+    assert!(!settings.is_maximized());
+}
+
+#[test]
+#[serial_test::serial]
+fn deny_unknown_keys_defined_in_macro() {
+    setup_schema();
+
+    // Skipping by `signature` would be an error, as every key has to be handled
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        deny_unknown_keys
+    )]
+    #[gen_settings_skip(key_name = "string-tuple")]
+    #[gen_settings_skip(key_name = "two-strings")]
+    #[gen_settings_skip(key_name = "cache-dir")]
+    #[gen_settings_skip(key_name = "data-dir")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    settings.set_window_width(640);
+    assert_eq!(settings.window_width(), 640);
+
+    settings.reset_window_width();
+}
+
+#[test]
+#[serial_test::serial]
+fn dump_func() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    settings.reset_all();
+    settings.set_theme("dark");
+
+    let dump = settings.dump();
+    let mut lines = dump.lines();
+    assert_eq!(
+        lines.next(),
+        Some("io.github.seadve.test (/io/github/seadve/)")
+    );
+    assert_eq!(lines.next(), Some("is-maximized: false (default)"));
+    assert_eq!(lines.next(), Some("theme: 'dark' (user)"));
+    assert_eq!(lines.count(), Settings::KEYS.len() - 2);
+
+    settings.reset_theme();
+}
+
+#[test]
+#[serial_test::serial]
+fn values_struct_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        values_struct = true
+    )]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    settings.reset_window_width();
+    settings.reset_window_height();
+    settings.reset_alert_sound();
+
+    let mut values = settings.values();
+    assert_eq!(values.window_width, settings.window_width());
+    assert_eq!(values.alert_sound, AlertSound::Bark);
+
+    values.window_width = 1200;
+    values.alert_sound = AlertSound::Glass;
+    values.invalid_words = vec!["teh".to_string()];
+
+    let changed_keys = Rc::new(RefCell::new(Vec::new()));
+    let changed_keys_clone = Rc::clone(&changed_keys);
+    let handler_id = settings.connect_changed(None, move |_, key| {
+        changed_keys_clone.borrow_mut().push(key.to_string());
+    });
+
+    settings.set_all(&values).unwrap();
+    assert_eq!(settings.window_width(), 1200);
+    assert_eq!(settings.alert_sound(), AlertSound::Glass);
+    assert_eq!(settings.invalid_words(), vec!["teh".to_string()]);
+    assert_eq!(settings.values(), values);
+    assert!(changed_keys.borrow().contains(&"window-width".to_string()));
+    // Unchanged keys are not written, so they keep following the default
+    assert!(!changed_keys.borrow().contains(&"window-height".to_string()));
+    assert!(settings.user_value("window-height").is_none());
+    // The settings themselves are not left in delay-apply mode
+    assert!(!settings.is_delay_apply());
+    settings.disconnect(handler_id);
+
+    // Keys whose value is the default are reset instead of written
+    values.window_width = 600;
+    settings.set_all(&values).unwrap();
+    assert_eq!(settings.window_width(), 600);
+    assert!(settings.user_value("window-width").is_none());
+
+    // Changes are left unapplied if the settings are already delayed
+    let delayed_settings = Settings::new();
+    delayed_settings.delay();
+    values.window_width = 1300;
+    delayed_settings.set_all(&values).unwrap();
+    assert!(delayed_settings.has_unapplied());
+    assert_eq!(settings.window_width(), 600);
+    delayed_settings.apply();
+    assert_eq!(settings.window_width(), 1300);
+
+    settings.reset_window_width();
+    settings.reset_alert_sound();
+    settings.reset_invalid_words();
+}
+
+#[test]
+#[serial_test::serial]
+fn set_all_func_unchanged_keys() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        values_struct = true
+    )]
+    pub struct Settings;
+
+    let settings = Settings::new();
+
+    let mut preset = settings.values();
+    preset.window_width = 1200;
+
+    let changed_keys = Rc::new(RefCell::new(Vec::new()));
+    let changed_keys_clone = Rc::clone(&changed_keys);
+    let handler_id = settings.connect_changed(None, move |_, key| {
+        changed_keys_clone.borrow_mut().push(key.to_string());
+    });
+
+    // Only the key that differs is written
+    settings.set_all(&preset).unwrap();
+    assert_eq!(settings.window_width(), 1200);
+    assert_eq!(settings.values(), preset);
+    assert_eq!(*changed_keys.borrow(), vec!["window-width".to_string()]);
+
+    changed_keys.borrow_mut().clear();
+    settings.set_all(&preset).unwrap();
+    assert!(changed_keys.borrow().is_empty());
+    assert!(!settings.is_delay_apply());
+
+    settings.disconnect(handler_id);
+    settings.reset_window_width();
+}
+
+#[test]
+#[serial_test::serial]
+fn doc_hidden_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_define(key_name = "window-width", doc_hidden = true)]
+    #[gen_settings_define(
+        key_name = "theme",
+        doc_hidden = true,
+        arg_type = "&str",
+        ret_type = "String"
+    )]
+    #[gen_settings_define(key_name = "preferred-audio-source", doc_hidden = false)]
+    pub struct Settings;
+
+    let settings = Settings::new("io.github.seadve.test");
+
+    // The methods are only hidden from the docs
+    settings.set_window_width(30);
+    assert_eq!(settings.window_width(), 30);
+    settings.reset_window_width();
+
+    settings.set_theme("dark");
+    let theme: String = settings.theme();
+    assert_eq!(theme, "dark");
+    settings.reset_theme();
+
+    assert_eq!(settings.preferred_audio_source_nick(), "microphone");
+}
+
+#[test]
+#[serial_test::serial]
+fn single_choice_key() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    pub struct Settings;
+
+    let settings = Settings::new("io.github.seadve.test");
+    assert_eq!(RenderBackend::VARIANTS, &[RenderBackend::Software]);
+    assert_eq!(RenderBackend::default(), RenderBackend::Software);
+    assert_eq!(settings.render_backend(), RenderBackend::Software);
+    assert_eq!(settings.render_backend_nick(), "software");
+
+    settings.set_render_backend(RenderBackend::Software);
+    assert_eq!(settings.render_backend(), RenderBackend::Software);
+    assert_eq!(
+        "software".parse::<RenderBackend>().unwrap(),
+        RenderBackend::Software
+    );
+    assert!("hardware".parse::<RenderBackend>().is_err());
+
+    settings.reset_render_backend();
+}
+
+#[test]
+#[serial_test::serial]
+fn schema_file_with_byte_order_mark() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/schemas/io.github.seadve.test.bom.gschema.xml",
+        id = "io.github.seadve.test.split"
+    )]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    assert_eq!(settings.layout(), Layout::Grid);
+
+    settings.set_layout(Layout::List);
+    assert_eq!(settings.layout(), Layout::List);
+
+    settings.reset_layout();
+}
+
+#[test]
+fn escaped_quotes_in_default() {
+    // The schema is not installed, so only check the defaults of the generated enums
+    #[gen_settings(xml = r#"
+            <schemalist>
+                <schema path="/io/github/seadve/test/quotes/" id="io.github.seadve.test.quotes">
+                    <key name="greeting" type="s">
+                        <choices>
+                            <choice value="It's here"/>
+                            <choice value="It's gone"/>
+                        </choices>
+                        <default>'It\'s here'</default>
+                    </key>
+                    <key name="farewell" type="s">
+                        <choices>
+                            <choice value="It's here"/>
+                            <choice value="It's gone"/>
+                        </choices>
+                        <default>"It's gone"</default>
+                    </key>
+                    <key name="quote" type="s">
+                        <choices>
+                            <choice value='Say "hi"'/>
+                            <choice value="Bye"/>
+                        </choices>
+                        <default>"Say \"hi\""</default>
+                    </key>
+                </schema>
+            </schemalist>
+        "#)]
+    pub struct Settings;
+
+    assert_eq!(Greeting::default(), Greeting::ItSHere);
+    assert_eq!(Farewell::default(), Farewell::ItSGone);
+    assert_eq!(Quote::default(), Quote::SayHi);
+}
+
+#[test]
+fn unit_key() {
+    // The schema is not installed, so only check the types of the generated methods
+    #[gen_settings(xml = r#"
+            <schemalist>
+                <schema path="/io/github/seadve/test/unit/" id="io.github.seadve.test.unit">
+                    <key name="marker" type="()">
+                        <default>()</default>
+                    </key>
+                </schema>
+            </schemalist>
+        "#)]
+    pub struct Settings;
+
+    let _: fn(&Settings) -> () = Settings::marker;
+    let _: fn(&Settings, ()) = Settings::set_marker;
+    let _: fn(&Settings) = Settings::reset_marker;
+}
+
+#[test]
+fn reserved_method_names() {
+    // Only the methods that are generated are reserved, and a key colliding
+    // with one can be skipped
+    #[gen_settings(xml = r#"
+            <schemalist>
+                <schema path="/io/github/seadve/test/reserved/" id="io.github.seadve.test.reserved">
+                    <key name="values" type="b">
+                        <default>false</default>
+                    </key>
+                    <key name="describe" type="b">
+                        <default>false</default>
+                    </key>
+                    <key name="schema-path" type="s">
+                        <default>''</default>
+                    </key>
+                </schema>
+            </schemalist>
+        "#)]
+    #[gen_settings_skip(key_name = "schema-path")]
+    pub struct Settings;
+
+    let _: fn(&Settings) -> bool = Settings::values;
+    let _: fn(&Settings) -> bool = Settings::describe;
+    let _: fn(&Settings) -> glib::GString = Settings::schema_path;
+}
+
+#[test]
+#[serial_test::serial]
+fn xml_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        xml = r#"
+            <schemalist>
+                <enum id="io.github.seadve.test.split.Layout">
+                    <value nick="grid" value="0"/>
+                    <value nick="list" value="1"/>
+                </enum>
+                <schema path="/io/github/seadve/test/split/" id="io.github.seadve.test.split">
+                    <key name="layout" enum="io.github.seadve.test.split.Layout">
+                        <default>
+                            'grid'
+                        </default>
+                    </key>
+                </schema>
+            </schemalist>
+        "#,
+        id = "io.github.seadve.test.split"
+    )]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    assert_eq!(settings.layout(), Layout::Grid);
+    assert_eq!(Layout::default(), Layout::Grid);
+
+    settings.set_layout(Layout::List);
+    assert_eq!(settings.layout(), Layout::List);
+
+    settings.reset_layout();
+}
+
+#[test]
+#[serial_test::serial]
+fn custom_crates() {
+    setup_schema();
+
+    mod inner {
+        // Shadows the `gio` crate, so that it would fail to compile
+        // if it is used directly
+        #[allow(dead_code)]
+        mod gio {}
+
+        mod reexport {
+            pub use ::gio::{self, glib};
+        }
+
+        use ::gio::glib;
+        use gsettings_macro::gen_settings;
+
+        #[gen_settings(
+            file = "./tests/io.github.seadve.test.gschema.xml",
+            id = "io.github.seadve.test",
+            gio_crate = "reexport::gio",
+            glib_crate = "reexport::glib"
+        )]
+        #[gen_settings_skip(signature = "ay")]
+        pub struct Settings;
+    }
+
+    let settings = inner::Settings::new();
+
+    settings.set_window_width(200);
+    assert_eq!(settings.window_width(), 200);
+
+    settings.set_alert_sound(inner::AlertSound::Drip);
+    assert_eq!(settings.alert_sound(), inner::AlertSound::Drip);
+
+    settings.set_space_style(inner::SpaceStyle::BEFORE_COLON);
+    assert_eq!(settings.space_style(), inner::SpaceStyle::BEFORE_COLON);
+
+    settings.reset_window_width();
+    settings.reset_alert_sound();
+    settings.reset_space_style();
+}
+
+#[test]
+#[serial_test::serial]
+fn path_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.relocatable.gschema.xml",
+        id = "io.github.seadve.test.relocatable",
+        path = "/io/github/seadve/test/profiles/first/"
+    )]
+    pub struct FirstProfileSettings;
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.relocatable.gschema.xml",
+        path = "/io/github/seadve/test/profiles/second/"
+    )]
+    pub struct SecondProfileSettings;
+
+    let first = FirstProfileSettings::new();
+    let second = SecondProfileSettings::new("io.github.seadve.test.relocatable");
+    assert_eq!(
+        first.schema_path(),
+        "/io/github/seadve/test/profiles/first/"
+    );
+    assert_eq!(
+        second.schema_path(),
+        "/io/github/seadve/test/profiles/second/"
+    );
+
+    first.set_name("First");
+    assert_eq!(first.name(), "First");
+    assert_eq!(second.name(), "Unnamed");
+
+    first.reset_name();
+}
+
+#[test]
+#[serial_test::serial]
+fn path_template_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.relocatable.gschema.xml",
+        id = "io.github.seadve.test.relocatable",
+        path_template = "/io/github/seadve/test/profiles/{}/"
+    )]
+    pub struct ProfileSettings;
+
+    let work = ProfileSettings::for_profile("work");
+    let home = ProfileSettings::for_profile("home");
+    assert_eq!(work.schema_path(), "/io/github/seadve/test/profiles/work/");
+    assert_eq!(home.schema_path(), "/io/github/seadve/test/profiles/home/");
+
+    work.set_volume(0.8);
+    assert_eq!(work.volume(), 0.8);
+    assert_eq!(home.volume(), 0.5);
+    assert_eq!(ProfileSettings::for_profile("work").volume(), 0.8);
+
+    work.reset_volume();
+}
+
+#[test]
+#[should_panic(expected = "invalid profile")]
+fn path_template_invalid_profile() {
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.relocatable.gschema.xml",
+        path_template = "/io/github/seadve/test/profiles/{}/"
+    )]
+    pub struct ProfileSettings;
+
+    ProfileSettings::for_profile("io.github.seadve.test.relocatable", "work/home");
+}
+
+#[test]
+#[serial_test::serial]
+fn private_struct() {
+    setup_schema();
+
+    mod inner {
+        use gio::glib;
+        use gsettings_macro::gen_settings;
+
+        #[gen_settings(
+            file = "./tests/io.github.seadve.test.gschema.xml",
+            id = "io.github.seadve.test"
+        )]
+        #[gen_settings_skip(signature = "(ss)")]
+        #[gen_settings_skip(signature = "ay")]
+        struct Settings;
+    }
+
+    // TODO: Use `trybuild` to test if these would cause failed compilation
+    //
+    // use inner::AlertSound;
+    // use inner::PreferredAudioSource;
+    // use inner::Settings;
+    // use inner::SpaceStyle;
+}
+
+#[test]
+#[serial_test::serial]
+fn field_name_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        field_name = "settings"
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    impl Settings {
+        fn raw_window_width(&self) -> i32 {
+            self.settings.int("window-width")
+        }
+    }
+
+    let settings = Settings::new();
+    settings.set_window_width(300);
+    assert_eq!(settings.raw_window_width(), 300);
+
+    let n_changed_calls = Rc::new(Cell::new(0));
+    let n_changed_calls_clone = Rc::clone(&n_changed_calls);
+    let handler_id = settings.connect_window_width_changed(move |settings| {
+        assert_eq!(settings.raw_window_width(), 400);
+        n_changed_calls_clone.set(n_changed_calls_clone.get() + 1);
+    });
+    settings.set_window_width(400);
+    assert_eq!(n_changed_calls.get(), 1);
+    settings.disconnect(handler_id);
+
+    let gio_settings = gio::Settings::from(&settings);
+    assert_eq!(Settings::from(gio_settings), settings);
+
+    let value = settings.to_value();
+    assert_eq!(value.get::<Settings>().unwrap(), settings);
+
+    settings.reset_window_width();
+}
+
+#[test]
+#[serial_test::serial]
+fn lazy_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        lazy = true
+    )]
+    pub struct Settings;
+
+    // Like the private struct of an object subclass
+    #[derive(Default)]
+    struct Imp {
+        settings: Settings,
+    }
+
+    let imp = Imp::default();
+    imp.settings.set_window_width(300);
+    assert_eq!(imp.settings.window_width(), 300);
+    assert_eq!(Settings::new().window_width(), 300);
+
+    let value = imp.settings.to_value();
+    let settings = value.get::<Settings>().unwrap();
+    assert_eq!(settings.window_width(), 300);
+    assert_eq!(gio::Settings::from(&settings), *imp.settings);
+
+    imp.settings.reset_window_width();
+}
+
+#[test]
+fn lazy_default_does_not_create_settings() {
+    #[gen_settings(
+        xml = r#"
+            <schemalist>
+                <schema path="/io/github/seadve/test/missing/" id="io.github.seadve.test.missing">
+                    <key name="is-enabled" type="b">
+                        <default>false</default>
+                    </key>
+                </schema>
+            </schemalist>
+        "#,
+        id = "io.github.seadve.test.missing",
+        lazy = true
+    )]
+    pub struct Settings;
+
+    // gio aborts if the schema is not installed, so this would fail if the
+    // settings were created
+    let _settings = Settings::default();
+}
+
+#[test]
+#[serial_test::serial]
+fn array_of_tuples_key() {
+    let settings = test_settings();
+
+    let recent_items: Vec<(u32, String, String)> = settings.recent_items();
+    assert_eq!(
+        recent_items,
+        vec![(
+            1,
+            String::from("Notes"),
+            String::from("/home/user/notes.txt")
+        )]
+    );
+
+    settings.set_recent_items(&[
+        (2, "Photo", "/home/user/photo.png"),
+        (3, "Song", "/home/user/song.ogg"),
+    ]);
+    assert_eq!(settings.recent_items().len(), 2);
+    assert_eq!(settings.recent_items()[1].1, "Song");
+
+    settings.set_recent_items(&[]);
+    assert!(settings.recent_items().is_empty());
+
+    settings.reset_recent_items();
+    assert!(settings.recent_items_is_default());
+}
+
+#[test]
+#[serial_test::serial]
+fn string_array_into_iter() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        string_array_into_iter = true
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+
+    settings.set_invalid_words(vec![String::from("invalid"), String::from("words")]);
+    assert_eq!(settings.invalid_words(), vec!["invalid", "words"]);
+
+    settings.set_invalid_words(["other"]);
+    assert_eq!(settings.invalid_words(), vec!["other"]);
+
+    settings.set_invalid_words(Vec::<&str>::new());
+    assert!(settings.invalid_words().is_empty());
+
+    settings.reset_invalid_words();
+}
+
+#[test]
+#[serial_test::serial]
+fn gstring_getters() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        gstring_getters = true,
+        try_getters = true,
+        values_struct = true
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+
+    let theme: gio::glib::GString = settings.theme();
+    assert_eq!(theme, "light");
+    assert_eq!(settings.theme_default_value(), "light");
+
+    settings.set_theme("dark");
+    assert_eq!(settings.try_theme().unwrap(), "dark");
+    assert_eq!(settings.replace_theme("light"), "dark");
+
+    let invalid_words: Vec<gio::glib::GString> = settings.invalid_words();
+    assert!(invalid_words.is_empty());
+
+    settings.set_invalid_words(&["invalid", "words"]);
+    assert_eq!(settings.invalid_words(), vec!["invalid", "words"]);
+
+    let mut values = settings.values();
+    values.theme = "dark".into();
+    values.invalid_words = vec!["other".into()];
+    settings.set_all(&values).unwrap();
+    assert_eq!(settings.theme(), "dark");
+    assert_eq!(settings.invalid_words(), vec!["other"]);
+
+    // Keys with choices are not affected
+    assert_eq!(
+        settings.preferred_audio_source(),
+        PreferredAudioSource::Microphone
+    );
+
+    settings.reset_theme();
+    settings.reset_invalid_words();
+}
+
+#[test]
+#[serial_test::serial]
+fn string_array_iter_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        string_array_iter = true
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    assert_eq!(settings.invalid_words_iter().count(), 0);
+
+    settings.set_invalid_words(&["invalid", "words"]);
+    let mut iter = settings.invalid_words_iter();
+    assert_eq!(iter.next().as_deref(), Some("invalid"));
+    assert_eq!(iter.next().as_deref(), Some("words"));
+    assert_eq!(iter.next(), None);
+
+    settings.reset_invalid_words();
+}
+
+#[test]
+#[serial_test::serial]
+fn static_getter_func() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let gio_settings = gio::Settings::new("io.github.seadve.test");
+    gio_settings.set_int("window-width", 250).unwrap();
+    gio_settings.set_string("alert-sound", "drip").unwrap();
+
+    assert_eq!(Settings::get_window_width(&gio_settings), 250);
+    assert_eq!(Settings::get_alert_sound(&gio_settings), AlertSound::Drip);
+    assert_eq!(Settings::get_theme(&gio_settings), Settings::new().theme());
+
+    gio_settings.reset("window-width");
+    gio_settings.reset("alert-sound");
+}
+
+#[test]
+#[serial_test::serial]
+fn dir_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        dir = "./tests",
+        id = "io.github.seadve.test.relocatable",
+        path = "/io/github/seadve/test/profiles/dir/"
+    )]
+    pub struct ProfileSettings;
+
+    let settings = ProfileSettings::new();
+    assert_eq!(
+        settings.schema_id().unwrap(),
+        "io.github.seadve.test.relocatable"
+    );
+    assert_eq!(ProfileSettings::KEYS, &["name", "volume"]);
+    assert_eq!(settings.name(), "Unnamed");
+}
+
+#[test]
+#[serial_test::serial]
+fn try_new_func() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::try_new("io.github.seadve.test").unwrap();
+    assert_eq!(settings.schema_id().unwrap(), "io.github.seadve.test");
+
+    let err = Settings::try_new("io.github.seadve.not-installed").unwrap_err();
+    assert!(err.to_string().contains("not installed"));
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.relocatable.gschema.xml",
+        id = "io.github.seadve.test.relocatable"
+    )]
+    pub struct ProfileSettings;
+
+    let err = ProfileSettings::try_new().unwrap_err();
+    assert!(err.to_string().contains("requires a path"));
+}
+
+#[test]
+#[serial_test::serial]
+fn annotated_default_value() {
+    let settings = test_settings();
+    assert_eq!(settings.last_project_default_value(), None);
+    assert_eq!(settings.last_project(), None);
+    assert_eq!(settings.invalid_words_default_value(), Vec::<String>::new());
+
+    settings.set_last_project(Some("gsettings-macro"));
+    assert_eq!(settings.last_project(), Some("gsettings-macro".to_string()));
+    assert!(!settings.last_project_is_default());
+
+    settings.reset_last_project();
+    assert!(settings.last_project_is_default());
+}
+
+#[test]
+#[serial_test::serial]
+fn range_func() {
+    let settings = test_settings();
+    assert_eq!(settings.volume_range(), (1.0, 10.0));
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.relocatable.gschema.xml",
+        id = "io.github.seadve.test.relocatable",
+        path = "/io/github/seadve/test/profiles/range/"
+    )]
+    pub struct ProfileSettings;
+
+    let profile_settings = ProfileSettings::new();
+    let (min, max) = profile_settings.volume_range();
+    assert!((min..=max).contains(&profile_settings.volume()));
+}
+
+#[test]
+#[serial_test::serial]
+fn multiple_files_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = [
+            "./tests/io.github.seadve.test.split.enums.gschema.xml",
+            "./tests/io.github.seadve.test.split.gschema.xml",
+        ],
+        id = "io.github.seadve.test.split"
+    )]
+    pub struct SplitSettings;
+
+    let settings = SplitSettings::new();
+    assert_eq!(settings.layout(), Layout::Grid);
+
+    settings.set_layout(Layout::List);
+    assert_eq!(settings.layout(), Layout::List);
+
+    settings.reset_layout();
+}
+
+#[test]
+#[serial_test::serial]
+fn multiple_structs_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.multi.gschema.xml",
+        ids = [
+            "io.github.seadve.test.multi.window",
+            "io.github.seadve.test.multi.editor",
+        ],
+        names = ["WindowSettings", "EditorSettings"]
+    )]
+    pub struct Settings;
+
+    let window_settings = WindowSettings::new();
+    let editor_settings = EditorSettings::default();
+
+    window_settings.set_width(800);
+    assert_eq!(window_settings.width(), 800);
+    assert_eq!(editor_settings.font_size(), 12);
+
+    // The type of the key is shared by both structs
+    editor_settings.set_color_scheme(ColorScheme::Dark);
+    assert_eq!(editor_settings.color_scheme(), ColorScheme::Dark);
+    assert_eq!(window_settings.color_scheme(), ColorScheme::Light);
+
+    window_settings.reset_width();
+    editor_settings.reset_color_scheme();
+}
+
+#[test]
+#[serial_test::serial]
+fn eq_by_schema_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.relocatable.gschema.xml",
+        id = "io.github.seadve.test.relocatable",
+        path_template = "/io/github/seadve/test/profiles/{}/",
+        eq_by_schema = true
+    )]
+    pub struct ProfileSettings;
+
+    let work_settings = ProfileSettings::for_profile("work");
+    let another_work_settings = ProfileSettings::for_profile("work");
+    let home_settings = ProfileSettings::for_profile("home");
+
+    assert_eq!(work_settings, another_work_settings);
+    assert_ne!(work_settings, home_settings);
+
+    let set = [work_settings, another_work_settings, home_settings]
+        .into_iter()
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+#[serial_test::serial]
+fn const_default_func() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml")]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    const IS_MAXIMIZED: bool = Settings::default_is_maximized();
+
+    let settings = Settings::new("io.github.seadve.test");
+    assert_eq!(IS_MAXIMIZED, settings.is_maximized_default_value());
+}
+
+#[test]
+#[serial_test::serial]
+#[deny(unused_must_use)]
+fn must_use_getters_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        must_use_getters
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    let _ = settings.window_width();
+    assert_eq!(
+        settings.window_width(),
+        Settings::get_window_width(&settings)
+    );
+}
+
+#[test]
+#[serial_test::serial]
+fn new_with_defaults_func() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new_with_defaults(&[
+        ("window-width", 1024.to_variant()),
+        ("alert-sound", AlertSound::Glass.to_variant()),
+    ]);
+    assert_eq!(settings.window_width(), 1024);
+    assert_eq!(settings.alert_sound(), AlertSound::Glass);
+    assert!(!settings.is_maximized());
+
+    // The values are only in the memory backend of these settings
+    assert_eq!(Settings::new().window_width(), 600);
+
+    settings.reset_window_width();
+    assert_eq!(settings.window_width(), 600);
+}
+
+#[test]
+#[serial_test::serial]
+fn builder_func() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.relocatable.gschema.xml",
+        id = "io.github.seadve.test.relocatable"
+    )]
+    pub struct ProfileSettings;
+
+    let backend = gio::memory_settings_backend_new();
+    let settings = ProfileSettings::builder()
+        .path("/io/github/seadve/test/profiles/builder/")
+        .backend(&backend)
+        .build();
+    assert_eq!(
+        settings.schema_path(),
+        "/io/github/seadve/test/profiles/builder/"
+    );
+    assert_eq!(settings.backend().unwrap(), backend);
+    assert!(!settings.is_delay_apply());
+
+    let settings = ProfileSettings::builder()
+        .path("/io/github/seadve/test/profiles/builder/")
+        .delayed(true)
+        .build();
+    assert!(settings.is_delay_apply());
+
+    settings.set_name("Builder");
+    assert!(settings.has_unapplied());
+    settings.apply();
+    assert_eq!(settings.name(), "Builder");
+
+    let settings = ProfileSettings::with_path_and_backend(
+        "/io/github/seadve/test/profiles/backend/",
+        &backend,
+    );
+    assert_eq!(
+        settings.schema_path(),
+        "/io/github/seadve/test/profiles/backend/"
+    );
+    assert_eq!(settings.backend().unwrap(), backend);
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::builder().build();
+    assert_eq!(settings.schema_path(), "/io/github/seadve/");
+    assert!(!settings.is_delay_apply());
+}
+
+#[test]
+#[serial_test::serial]
+fn boolean_array() {
+    let settings = test_settings();
+    assert_eq!(settings.toggle_states_default_value(), vec![true, false]);
+
+    settings.set_toggle_states(&[false, true, true, false]);
+    assert_eq!(settings.toggle_states(), vec![false, true, true, false]);
+
+    settings.set_toggle_states(&[]);
+    assert!(settings.toggle_states().is_empty());
+
+    settings.reset_toggle_states();
+}
+
+#[test]
+#[serial_test::serial]
+fn getter_or_func() {
+    let settings = test_settings();
+    settings.set_window_width(200);
+    assert_eq!(settings.window_width_or(100), 200);
+    settings.reset_window_width();
+
+    // Simulate an older installed schema that doesn't have the keys
+    let settings = Settings(gio::Settings::new("io.github.seadve.test.split"));
+    assert_eq!(settings.window_width_or(100), 100);
+    assert_eq!(
+        settings.alert_sound_or(AlertSound::Glass),
+        AlertSound::Glass
+    );
+}
+
+#[test]
+#[serial_test::serial]
+fn connect_reset_func() {
+    let settings = test_settings();
+
+    let n_reset_calls = Rc::new(Cell::new(0));
+    let n_reset_calls_clone = Rc::clone(&n_reset_calls);
+    let handler_id = settings.connect_window_width_reset(move |settings| {
+        assert!(settings.window_width_is_default());
+        n_reset_calls_clone.set(n_reset_calls_clone.get() + 1);
+    });
+
+    settings.set_window_width(200);
+    assert_eq!(n_reset_calls.get(), 0);
+
+    settings.set_window_width(300);
+    assert_eq!(n_reset_calls.get(), 0);
+
+    settings.reset_window_width();
+    assert_eq!(n_reset_calls.get(), 1);
+
+    settings.disconnect(handler_id);
+}
+
+#[test]
+#[serial_test::serial]
+fn connect_flags_changed_func() {
+    let settings = test_settings();
+
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let values_clone = Rc::clone(&values);
+    let handler_id = settings.connect_space_style_changed(move |settings, value| {
+        assert_eq!(settings.space_style(), value);
+        values_clone.borrow_mut().push(value);
+    });
+
+    settings.set_space_style(SpaceStyle::BEFORE_SEMICOLON);
+    settings.set_space_style(SpaceStyle::BEFORE_SEMICOLON | SpaceStyle::BEFORE_COMMA);
+    settings.reset_space_style();
+
+    assert_eq!(
+        *values.borrow(),
+        vec![
+            SpaceStyle::BEFORE_SEMICOLON,
+            SpaceStyle::BEFORE_SEMICOLON | SpaceStyle::BEFORE_COMMA,
+            SpaceStyle::empty(),
+        ]
+    );
+
+    settings.disconnect(handler_id);
+}
+
+#[test]
+#[serial_test::serial]
+fn connect_changed_weak_func() {
+    let settings = test_settings();
+    let action = gio::SimpleAction::new("action", None);
+
+    let n_changed_calls = Rc::new(Cell::new(0));
+    let n_changed_calls_clone = Rc::clone(&n_changed_calls);
+    let handler_id =
+        settings.connect_window_width_changed_weak(&action.downgrade(), move |action, settings| {
+            action.set_enabled(settings.window_width() < 300);
+            n_changed_calls_clone.set(n_changed_calls_clone.get() + 1);
+        });
+
+    settings.set_window_width(400);
+    assert_eq!(n_changed_calls.get(), 1);
+    assert!(!action.is_enabled());
+
+    drop(action);
+
+    settings.set_window_width(200);
+    assert_eq!(n_changed_calls.get(), 1);
+
+    settings.disconnect(handler_id);
+    settings.reset_window_width();
+}
+
+#[test]
+#[serial_test::serial]
+fn no_constructor_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(file = "./tests/io.github.seadve.test.gschema.xml", no_constructor)]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::from_settings(gio::Settings::new("io.github.seadve.test"));
+    settings.set_window_width(200);
+    assert_eq!(settings.window_width(), 200);
+    settings.reset_window_width();
+}
+
+#[test]
+#[serial_test::serial]
+fn constructor_visibility_defined_in_macro() {
+    setup_schema();
+
+    mod settings {
+        use gio::glib;
+        use gsettings_macro::gen_settings;
+
+        #[gen_settings(
+            file = "./tests/io.github.seadve.test.gschema.xml",
+            id = "io.github.seadve.test",
+            constructor_visibility = "pub(super)"
+        )]
+        #[gen_settings_skip(signature = "(ss)")]
+        #[gen_settings_skip(signature = "ay")]
+        pub struct Settings;
+    }
+
+    let settings = settings::Settings::new();
+    settings.set_window_width(200);
+    assert_eq!(settings.window_width(), 200);
+    settings.reset_window_width();
+
+    let settings = settings::Settings::from_settings(gio::Settings::new("io.github.seadve.test"));
+    assert_eq!(settings.window_width(), 600);
+}
+
+#[test]
+#[serial_test::serial]
+fn enum_name_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    #[gen_settings_define(key_name = "alert-sound", enum_name = "Sound")]
+    #[gen_settings_define(key_name = "space-style", enum_name = "SpacingFlags")]
+    #[gen_settings_define(key_name = "preferred-audio-source", enum_name = "AudioSource")]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+
+    settings.set_alert_sound(Sound::Glass);
+    assert_eq!(settings.alert_sound(), Sound::Glass);
+    assert_eq!("drip".parse::<Sound>().unwrap(), Sound::Drip);
+    assert_eq!(Sound::default(), Sound::Bark);
+
+    settings.set_space_style(SpacingFlags::BEFORE_COMMA);
+    assert_eq!(settings.space_style(), SpacingFlags::BEFORE_COMMA);
+
+    settings.set_preferred_audio_source(AudioSource::DesktopAudio);
+    assert_eq!(settings.preferred_audio_source(), AudioSource::DesktopAudio);
+
+    settings.reset_alert_sound();
+    settings.reset_space_style();
+    settings.reset_preferred_audio_source();
+}
+
+#[test]
+#[serial_test::serial]
+fn reset_in_delay_mode() {
+    let settings = test_settings();
+    settings.set_window_width(200);
+    settings.set_window_height(300);
+
+    let delayed_settings = Settings::builder().delayed(true).build();
+    delayed_settings.reset_window_width();
+    delayed_settings.reset_window_height();
+    assert!(delayed_settings.has_unapplied());
+
+    // Nothing is written until the changes are applied
+    assert_eq!(settings.window_width(), 200);
+    assert_eq!(settings.window_height(), 300);
+
+    delayed_settings.apply();
+    assert!(settings.window_width_is_default());
+    assert!(settings.window_height_is_default());
+
+    settings.set_window_width(200);
+    settings.set_alert_sound(AlertSound::Glass);
+
+    delayed_settings.reset_all();
+    assert_eq!(settings.window_width(), 200);
+    assert_eq!(settings.alert_sound(), AlertSound::Glass);
+
+    delayed_settings.apply();
+    assert!(Settings::KEYS
+        .iter()
+        .all(|key| settings.user_value(key).is_none()));
+}
+
+#[test]
+#[serial_test::serial]
+fn bind_inverted_func() {
+    let settings = test_settings();
+    let action = gio::SimpleAction::new("hidden", None);
+    settings
+        .bind_is_maximized_inverted(&action, "enabled")
+        .build();
+
+    assert!(!settings.is_maximized());
+    assert!(action.is_enabled());
+
+    settings.set_is_maximized(true);
+    assert!(!action.is_enabled());
+
+    action.set_enabled(true);
+    assert!(!settings.is_maximized());
+
+    settings.reset_is_maximized();
+}
+
+#[test]
+#[serial_test::serial]
+fn replace_func() {
+    let settings = test_settings();
+
+    assert_eq!(settings.replace_window_width(200), 600);
+    assert_eq!(settings.replace_window_width(300), 200);
+    assert_eq!(settings.window_width(), 300);
+
+    assert_eq!(
+        settings.replace_alert_sound(AlertSound::Glass),
+        AlertSound::Bark
+    );
+    assert_eq!(settings.alert_sound(), AlertSound::Glass);
+
+    settings.reset_window_width();
+    settings.reset_alert_sound();
+}
+
+#[test]
+#[serial_test::serial]
+fn integer_double_default_value() {
+    let settings = test_settings();
+    assert_eq!(settings.playback_speed_default_value(), 1.0);
+    assert_eq!(settings.playback_speed(), 1.0);
+
+    settings.set_playback_speed(1.5);
+    assert_eq!(settings.playback_speed(), 1.5);
+
+    settings.reset_playback_speed();
+}
+
+#[test]
+#[serial_test::serial]
+fn connect_changed_debounced_func() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        connect_changed_debounced = true
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let values_clone = Rc::clone(&values);
+    let handler_id = settings.connect_window_width_changed_debounced(
+        Duration::from_millis(10),
+        move |_, value| {
+            values_clone.borrow_mut().push(*value);
+        },
+    );
+
+    settings.set_window_width(100);
+    settings.set_window_width(200);
+    settings.set_window_width(300);
+    assert!(values.borrow().is_empty());
+
+    let context = glib::MainContext::default();
+    while values.borrow().is_empty() {
+        context.iteration(true);
+    }
+    assert_eq!(*values.borrow(), vec![300]);
+
+    // Disconnecting cancels the pending call
+    settings.set_window_width(400);
+    settings.disconnect(handler_id);
+    let timeout_fired = Rc::new(Cell::new(false));
+    let timeout_fired_clone = Rc::clone(&timeout_fired);
+    glib::timeout_add_local_once(Duration::from_millis(50), move || {
+        timeout_fired_clone.set(true);
+    });
+    while !timeout_fired.get() {
+        context.iteration(true);
+    }
+    assert_eq!(*values.borrow(), vec![300]);
+
+    settings.reset_window_width();
+}
+
+#[test]
+#[serial_test::serial]
+fn connect_changed_with_old_value_func() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        connect_changed_with_old_value = true
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    let changes_clone = Rc::clone(&changes);
+    let handler_id =
+        settings.connect_alert_sound_changed_with_old_value(move |_, old_value, value| {
+            changes_clone.borrow_mut().push((*old_value, *value));
+        });
+
+    settings.set_alert_sound(AlertSound::Glass);
+    settings.set_alert_sound(AlertSound::Drip);
+    settings.reset_alert_sound();
+
+    assert_eq!(
+        *changes.borrow(),
+        vec![
+            (AlertSound::Bark, AlertSound::Glass),
+            (AlertSound::Glass, AlertSound::Drip),
+            (AlertSound::Drip, AlertSound::Bark),
+        ]
+    );
+
+    settings.disconnect(handler_id);
+
+    let flag_changes = Rc::new(RefCell::new(Vec::new()));
+    let flag_changes_clone = Rc::clone(&flag_changes);
+    let handler_id =
+        settings.connect_space_style_changed_with_old_value(move |_, old_value, value| {
+            flag_changes_clone.borrow_mut().push((*old_value, *value));
+        });
+
+    settings.set_space_style(SpaceStyle::BEFORE_COLON);
+    settings.set_space_style(SpaceStyle::BEFORE_COLON | SpaceStyle::BEFORE_COMMA);
+    settings.reset_space_style();
+
+    assert_eq!(
+        *flag_changes.borrow(),
+        vec![
+            (SpaceStyle::empty(), SpaceStyle::BEFORE_COLON),
+            (
+                SpaceStyle::BEFORE_COLON,
+                SpaceStyle::BEFORE_COLON | SpaceStyle::BEFORE_COMMA
+            ),
+            (
+                SpaceStyle::BEFORE_COLON | SpaceStyle::BEFORE_COMMA,
+                SpaceStyle::empty()
+            ),
+        ]
+    );
+
+    settings.disconnect(handler_id);
+}
+
+#[test]
+#[serial_test::serial]
+fn bind_with_mapping_func() {
+    let settings = test_settings();
+    let object = gio::Application::new(None, gio::ApplicationFlags::empty());
+
+    settings
+        .bind_window_height_64_with_mapping(
+            &object,
+            "resource-base-path",
+            |value| Some(format!("/{}", value)),
+            |value| {
+                value
+                    .get::<Option<String>>()
+                    .ok()
+                    .flatten()?
+                    .strip_prefix('/')?
+                    .parse::<u64>()
+                    .ok()
+            },
+        )
+        .build();
+    assert_eq!(object.resource_base_path().unwrap(), "/400");
+
+    // Would lose precision if it is converted through `f64`
+    settings.set_window_height_64(u64::MAX);
+    assert_eq!(
+        object.resource_base_path().unwrap(),
+        format!("/{}", u64::MAX)
+    );
+
+    object.set_resource_base_path(Some(&format!("/{}", u64::MAX - 1)));
+    assert_eq!(settings.window_height_64(), u64::MAX - 1);
+
+    settings.reset_window_height_64();
+}
+
+#[test]
+#[serial_test::serial]
+fn try_getters_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        try_getters = true
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    // Mismatched with the `u` type of the key, so reading it fails
+    #[gen_settings_define(key_name = "window-height", arg_type = "u32", ret_type = "i32")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    assert_eq!(settings.try_window_width().unwrap(), 600);
+    assert_eq!(settings.try_alert_sound().unwrap(), AlertSound::Bark);
+
+    let err = settings.try_window_height().unwrap_err();
+    assert!(err.message.contains("window-height"));
+}
+
+#[test]
+#[serial_test::serial]
+fn assert_not_send_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        assert_not_send = true
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    assert_eq!(settings.window_width(), 600);
+}
+
+#[test]
+#[serial_test::serial]
+fn accessor_trait_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.relocatable.gschema.xml",
+        id = "io.github.seadve.test.relocatable",
+        path = "/io/github/seadve/test/profiles/accessor/",
+        accessor_trait = true
+    )]
+    pub struct ProfileSettings;
+
+    #[derive(Default)]
+    struct MockProfileSettings {
+        name: RefCell<String>,
+        volume: Cell<f64>,
+    }
+
+    impl ProfileSettingsExt for MockProfileSettings {
+        fn name(&self) -> String {
+            self.name.borrow().clone()
+        }
+
+        fn set_name(&self, value: &str) {
+            self.name.replace(value.to_string());
+        }
+
+        fn reset_name(&self) {
+            self.name.replace("Unnamed".to_string());
+        }
+
+        fn volume(&self) -> f64 {
+            self.volume.get()
+        }
+
+        fn set_volume(&self, value: f64) {
+            self.volume.set(value);
+        }
+
+        fn reset_volume(&self) {
+            self.volume.set(0.5);
+        }
+    }
+
+    fn rename(settings: &impl ProfileSettingsExt, name: &str) -> String {
+        let old_name = settings.name();
+        settings.set_name(name);
+        old_name
+    }
+
+    let mock = MockProfileSettings::default();
+    mock.reset_name();
+    assert_eq!(rename(&mock, "Mock"), "Unnamed");
+    assert_eq!(mock.name(), "Mock");
+    mock.set_volume(0.8);
+    assert_eq!(mock.volume(), 0.8);
+    mock.reset_volume();
+
+    let settings = ProfileSettings::new();
+    assert_eq!(rename(&settings, "Real"), "Unnamed");
+    assert_eq!(settings.name(), "Real");
+    ProfileSettingsExt::reset_name(&settings);
+    assert_eq!(settings.name(), "Unnamed");
+}
+
+#[test]
+#[serial_test::serial]
+fn settings_ext_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        settings_ext = true
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = gio::Settings::new("io.github.seadve.test");
+
+    settings.set_window_width(1200);
+    assert_eq!(settings.window_width(), 1200);
+    assert_eq!(
+        Settings::from_settings(settings.clone()).window_width(),
+        1200
+    );
+
+    assert!(settings.try_set_alert_sound(AlertSound::Glass).is_ok());
+    assert_eq!(settings.alert_sound(), AlertSound::Glass);
+
+    let n_changed_calls = Rc::new(Cell::new(0));
+    let n_changed_calls_clone = Rc::clone(&n_changed_calls);
+    let handler_id = settings.connect_window_width_changed(move |_| {
+        n_changed_calls_clone.set(n_changed_calls_clone.get() + 1);
+    });
+
+    settings.reset_window_width();
+    assert_eq!(n_changed_calls.get(), 1);
+    assert_eq!(settings.window_width(), 600);
+
+    settings.disconnect(handler_id);
+    settings.reset_alert_sound();
+}
+
+#[test]
+#[serial_test::serial]
+fn local_scope() {
+    setup_schema();
+
+    let alert_sound = {
+        #[gen_settings(
+            file = "./tests/io.github.seadve.test.gschema.xml",
+            id = "io.github.seadve.test"
+        )]
+        #[gen_settings_skip(signature = "(ss)")]
+        #[gen_settings_skip(signature = "ay")]
+        struct Settings;
+
+        Settings::default().alert_sound_nick()
+    };
+
+    // The same schema can be used again in a separate block in the same function
+    let other_alert_sound = {
+        #[gen_settings(
+            file = "./tests/io.github.seadve.test.gschema.xml",
+            id = "io.github.seadve.test"
+        )]
+        #[gen_settings_skip(signature = "(ss)")]
+        #[gen_settings_skip(signature = "ay")]
+        struct Settings;
+
+        Settings::default().alert_sound() == AlertSound::Bark
+    };
+
+    assert_eq!(alert_sound, "bark");
+    assert!(other_alert_sound);
+}
+
+#[test]
+#[serial_test::serial]
+fn small_integer_arrays() {
+    let settings = test_settings();
+    assert_eq!(settings.listen_ports(), vec![8080, 8081]);
+    assert_eq!(settings.column_offsets(), vec![0, -4]);
+
+    settings.set_listen_ports(&[u16::MAX, 0]);
+    assert_eq!(settings.listen_ports(), vec![u16::MAX, 0]);
+
+    settings.set_column_offsets(&[i16::MIN, i16::MAX]);
+    assert_eq!(settings.column_offsets(), vec![i16::MIN, i16::MAX]);
+
+    settings.reset_listen_ports();
+    settings.reset_column_offsets();
+}
+
+#[test]
+#[serial_test::serial]
+fn element_type_defined_in_macro() {
+    setup_schema();
+
+    #[derive(Debug, PartialEq)]
+    pub struct Word(String);
+
+    impl glib::StaticVariantType for Word {
+        fn static_variant_type() -> std::borrow::Cow<'static, glib::VariantTy> {
+            String::static_variant_type()
+        }
+    }
+
+    impl glib::ToVariant for Word {
+        fn to_variant(&self) -> glib::Variant {
+            self.0.to_variant()
+        }
+    }
+
+    impl glib::FromVariant for Word {
+        fn from_variant(variant: &glib::Variant) -> Option<Self> {
+            variant.get::<String>().map(Word)
+        }
+    }
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    #[gen_settings_define(
+        key_name = "invalid-words",
+        element_arg_type = "Word",
+        element_ret_type = "Word"
+    )]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    assert!(settings.invalid_words().is_empty());
+
+    settings.set_invalid_words(&[Word("teh".to_string()), Word("adn".to_string())]);
+    assert_eq!(
+        settings.invalid_words(),
+        vec![Word("teh".to_string()), Word("adn".to_string())]
+    );
+    assert_eq!(
+        settings.get::<Vec<String>>("invalid-words"),
+        vec!["teh".to_string(), "adn".to_string()]
+    );
+
+    settings.reset_invalid_words();
+}
+
+#[test]
+#[serial_test::serial]
+fn scalar_tuples() {
+    let settings = test_settings();
+    assert_eq!(settings.tile_size(), (16, 16));
+    assert_eq!(settings.panel_visibility(), (true, false));
+
+    settings.set_tile_size((u8::MAX, 0));
+    assert_eq!(settings.tile_size(), (u8::MAX, 0));
+
+    settings.set_panel_visibility((false, true));
+    assert_eq!(settings.panel_visibility(), (false, true));
+
+    settings.reset_tile_size();
+    settings.reset_panel_visibility();
+}
+
+#[test]
+#[serial_test::serial]
+fn mixed_numeric_tuples() {
+    let settings = test_settings();
+    assert_eq!(settings.volume_preset(), ("default".to_string(), 6.3));
+    assert_eq!(settings.zoom_level(), (1, 1.0));
+
+    settings.set_volume_preset(("loud", 9.5));
+    assert_eq!(settings.volume_preset(), ("loud".to_string(), 9.5));
+
+    // Tuples can only be bound with a mapping, even to variant properties
+    let action = gio::SimpleAction::new_stateful("zoom", None, &(0, 0.0).to_variant());
+    settings
+        .bind_zoom_level_with_mapping(
+            &action,
+            "state",
+            |zoom_level| Some(zoom_level.to_variant()),
+            |value| value.get::<glib::Variant>().ok()?.get(),
+        )
+        .build();
+    assert_eq!(action.state(), Some((1, 1.0).to_variant()));
+
+    settings.set_zoom_level((2, 1.5));
+    assert_eq!(action.state(), Some((2, 1.5).to_variant()));
+
+    action.set_state(&(3, 2.0).to_variant());
+    assert_eq!(settings.zoom_level(), (3, 2.0));
+
+    let label = gio::SimpleAction::new("label", None);
+    settings
+        .bind_volume_preset_with_mapping(
+            &label,
+            "enabled",
+            |(_, volume)| Some(volume > 5.0),
+            |value| {
+                let enabled = value.get::<bool>().ok()?;
+                Some(("mapped".to_string(), if enabled { 10.0 } else { 1.0 }))
+            },
+        )
+        .build();
+    assert!(label.is_enabled());
+
+    label.set_enabled(false);
+    assert_eq!(settings.volume_preset(), ("mapped".to_string(), 1.0));
+
+    let zoom_action = settings.create_zoom_level_action();
+    zoom_action.change_state(&(4, 3.0).to_variant());
+    assert_eq!(settings.zoom_level(), (4, 3.0));
+
+    settings.reset_volume_preset();
+    settings.reset_zoom_level();
+}
+
+#[test]
+#[serial_test::serial]
+fn bind_components_func() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test"
+    )]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    settings.set_two_strings(("io.github.initial", "io.github.second"));
+
+    let first = gio::Application::new(None, gio::ApplicationFlags::FLAGS_NONE);
+    let second = gio::Application::new(None, gio::ApplicationFlags::FLAGS_NONE);
+    settings.bind_two_strings_components(&first, "application-id", &second, "application-id");
+    assert_eq!(first.application_id().as_deref(), Some("io.github.initial"));
+    assert_eq!(second.application_id().as_deref(), Some("io.github.second"));
+
+    settings.set_two_strings(("io.github.first", "io.github.second"));
+    assert_eq!(first.application_id().as_deref(), Some("io.github.first"));
+    assert_eq!(second.application_id().as_deref(), Some("io.github.second"));
+
+    // Writing one property keeps the other component
+    first.set_application_id(Some("io.github.changed"));
+    assert_eq!(
+        settings.two_strings(),
+        (
+            "io.github.changed".to_string(),
+            "io.github.second".to_string()
+        )
+    );
+
+    second.set_application_id(Some("io.github.other"));
+    assert_eq!(
+        settings.two_strings(),
+        (
+            "io.github.changed".to_string(),
+            "io.github.other".to_string()
+        )
+    );
+
+    let start_panel = gio::SimpleAction::new("start-panel", None);
+    let end_panel = gio::SimpleAction::new("end-panel", None);
+    settings.bind_panel_visibility_components(&start_panel, "enabled", &end_panel, "enabled");
+    settings.set_panel_visibility((false, true));
+    assert!(!start_panel.is_enabled());
+    assert!(end_panel.is_enabled());
+
+    end_panel.set_enabled(false);
+    assert_eq!(settings.panel_visibility(), (false, false));
+
+    // The defaults are not valid application ids
+    drop((first, second));
+    settings.reset_two_strings();
+    settings.reset_panel_visibility();
+}
+
+#[test]
+#[serial_test::serial]
+fn bind_components_small_integers() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.object.gschema.xml",
+        id = "io.github.seadve.test.object"
+    )]
+    pub struct Settings;
+
+    // There are no 16-bit properties, so the components are bound to the
+    // wider ones
+    let settings = Settings::new();
+    let listener = gio::SocketListener::new();
+    let application = gio::Application::new(None, gio::ApplicationFlags::FLAGS_NONE);
+    settings.bind_backlog_and_timeout_components(
+        &listener,
+        "listen-backlog",
+        &application,
+        "inactivity-timeout",
+    );
+    assert_eq!(listener.property::<i32>("listen-backlog"), 10);
+    assert_eq!(application.inactivity_timeout(), 8080);
+
+    listener.set_property("listen-backlog", 20);
+    assert_eq!(settings.backlog_and_timeout(), (20, 8080));
+
+    // The value is only written if it fits in the component
+    application.set_inactivity_timeout(70000);
+    assert_eq!(settings.backlog_and_timeout(), (20, 8080));
+
+    application.set_inactivity_timeout(443);
+    assert_eq!(settings.backlog_and_timeout(), (20, 443));
+
+    settings.reset_backlog_and_timeout();
+}
+
+#[test]
+#[serial_test::serial]
+fn vardict() {
+    let settings = test_settings();
+    let default_options = settings.extra_options();
+    assert_eq!(default_options.len(), 2);
+    assert_eq!(default_options["name"].get::<String>().unwrap(), "default");
+    assert_eq!(default_options["priority"].get::<u32>().unwrap(), 0);
+    assert_eq!(settings.extra_options_default_value(), default_options);
+
+    let options = HashMap::from([
+        ("name".to_string(), "extension".to_variant()),
+        ("priority".to_string(), 10_u32.to_variant()),
+        ("enabled".to_string(), true.to_variant()),
+    ]);
+    settings.set_extra_options(&options);
+
+    let extra_options = settings.extra_options();
+    assert_eq!(extra_options, options);
+    assert_eq!(extra_options["name"].get::<String>().unwrap(), "extension");
+    assert_eq!(extra_options["priority"].get::<u32>().unwrap(), 10);
+    assert!(extra_options["enabled"].get::<bool>().unwrap());
+
+    settings.reset_extra_options();
+}
+
+#[test]
+#[serial_test::serial]
+fn gtk_expressions_defined_in_macro() {
+    setup_schema();
+
+    // Stands in for the `gtk` crate, mirroring the signatures of the
+    // expression constructors
+    mod mock_gtk {
+        use gio::glib;
+
+        pub struct ConstantExpression(pub glib::Value);
+
+        impl ConstantExpression {
+            pub fn new(value: impl glib::value::ToValue) -> Self {
+                Self(value.to_value())
+            }
+        }
+
+        pub struct PropertyExpression {
+            pub this_type: glib::Type,
+            pub expression: Option<ConstantExpression>,
+            pub property_name: String,
+        }
+
+        impl PropertyExpression {
+            pub fn new(
+                this_type: glib::Type,
+                expression: Option<ConstantExpression>,
+                property_name: &str,
+            ) -> Self {
+                Self {
+                    this_type,
+                    expression,
+                    property_name: property_name.to_string(),
+                }
+            }
+        }
+    }
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        gtk_crate = "mock_gtk"
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    let settings = Settings::new();
+    let expression = settings.window_width_expression();
+    assert_eq!(expression.this_type, gio::Action::static_type());
+    assert_eq!(expression.property_name, "state");
+
+    let action = expression
+        .expression
+        .unwrap()
+        .0
+        .get::<gio::Action>()
+        .unwrap();
+    assert_eq!(
+        action.property::<glib::Variant>(&expression.property_name),
+        600.to_variant()
+    );
+
+    settings.set_window_width(100);
+    assert_eq!(
+        action.property::<glib::Variant>(&expression.property_name),
+        100.to_variant()
+    );
+
+    settings.reset_window_width();
+}
+
+#[test]
+fn gtk_expressions_with_gtk_crate() {
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        gtk_crate = "gtk"
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct Settings;
+
+    // Evaluating the expression needs GTK to be initialized, which requires a
+    // display, so this only checks that it compiles against the `gtk` crate
+    let _: fn(&Settings) -> gtk::PropertyExpression = Settings::window_width_expression;
+    let _: fn(&Settings) -> gtk::PropertyExpression = Settings::alert_sound_expression;
+}
+
+#[test]
+#[serial_test::serial]
+fn object_defined_in_macro() {
+    setup_schema();
+
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.gschema.xml",
+        id = "io.github.seadve.test",
+        object = true
+    )]
+    #[gen_settings_skip(signature = "(ss)")]
+    #[gen_settings_skip(signature = "ay")]
+    pub struct ObjectSettings;
+
+    let settings = ObjectSettings::new();
+    let object = ObjectSettingsObject::new(&settings);
+    assert_eq!(object.settings(), *settings);
+
+    let notified = Rc::new(Cell::new(0));
+    let notified_clone = Rc::clone(&notified);
+    object.connect_notify_local(Some("window-width"), move |_, _| {
+        notified_clone.set(notified_clone.get() + 1);
+    });
+
+    assert_eq!(object.property::<i32>("window-width"), 600);
+    settings.set_window_width(100);
+    assert_eq!(object.property::<i32>("window-width"), 100);
+    assert_eq!(notified.get(), 1);
+
+    object.set_property("window-width", 200);
+    assert_eq!(settings.window_width(), 200);
+    assert_eq!(notified.get(), 2);
+
+    object.set_property("alert-sound", "glass");
+    assert_eq!(settings.alert_sound(), AlertSound::Glass);
+    object.set_property("alert-sound", None::<String>);
+    assert_eq!(settings.alert_sound(), AlertSound::Bark);
+
+    assert_eq!(
+        object.property::<glib::Variant>("dimensions"),
+        (10, 10).to_variant()
+    );
+    object.set_property("dimensions", (20, 30).to_variant());
+    assert_eq!(settings.dimensions(), (20, 30));
+
+    // Skipped keys have no property
+    assert!(object.find_property("cache-dir").is_none());
+
+    settings.reset_window_width();
+    settings.reset_dimensions();
+}
+
+#[test]
+#[serial_test::serial]
+fn object_small_integers() {
+    setup_schema();
+
+    // Named like the struct of `object_defined_in_macro`, which registers its
+    // object type in the same process
+    #[gen_settings(
+        file = "./tests/io.github.seadve.test.object.gschema.xml",
+        id = "io.github.seadve.test.object",
+        object = true
+    )]
+    pub struct ObjectSettings;
+
+    let settings = ObjectSettings::new();
+    let object = ObjectSettingsObject::new(&settings);
+    assert_eq!(
+        object.type_().name(),
+        "IoGithubSeadveTestObjectObjectSettingsObject"
+    );
+
+    assert_eq!(object.property::<u8>("brightness"), 128);
+    object.set_property("brightness", 64_u8);
+    assert_eq!(settings.brightness(), 64);
+
+    assert_eq!(object.property::<i32>("offset"), -4);
+    object.set_property("offset", -300);
+    assert_eq!(settings.offset(), -300);
+
+    let pspec = object
+        .find_property("port")
+        .unwrap()
+        .downcast::<glib::ParamSpecUInt>()
+        .unwrap();
+    assert_eq!(pspec.minimum(), 0);
+    assert_eq!(pspec.maximum(), u32::from(u16::MAX));
+    assert_eq!(object.property::<u32>("port"), 8080);
+    object.set_property("port", 443_u32);
+    assert_eq!(settings.port(), 443);
+
+    settings.reset_brightness();
+    settings.reset_offset();
+    settings.reset_port();
+}
+
+#[test]
+#[serial_test::serial]
+#[should_panic(expected = "has already been registered")]
+fn object_same_name_in_different_modules() {
+    setup_schema();
+
+    // The module is not part of the name of the registered type, so the
+    // objects of both structs have the same type name
+    mod first {
+        use gio::glib;
+        use gsettings_macro::gen_settings;
+
+        #[gen_settings(
+            file = "./tests/io.github.seadve.test.object.gschema.xml",
+            id = "io.github.seadve.test.object",
+            object = true
+        )]
+        pub struct DuplicateSettings;
+    }
+
+    mod second {
+        use gio::glib;
+        use gsettings_macro::gen_settings;
+
+        #[gen_settings(
+            file = "./tests/io.github.seadve.test.object.gschema.xml",
+            id = "io.github.seadve.test.object",
+            object = true
+        )]
+        pub struct DuplicateSettings;
+    }
+
+    let first_object = first::DuplicateSettingsObject::new(&first::DuplicateSettings::new());
+    assert_eq!(
+        first_object.type_().name(),
+        "IoGithubSeadveTestObjectDuplicateSettingsObject"
+    );
+    second::DuplicateSettingsObject::new(&second::DuplicateSettings::new());
 }
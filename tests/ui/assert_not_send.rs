@@ -0,0 +1,21 @@
+use gio::glib;
+use gsettings_macro::gen_settings;
+
+#[gen_settings(
+    xml = r#"
+        <schemalist>
+            <schema path="/io/github/seadve/test/ui/" id="io.github.seadve.test.ui">
+                <key name="width" type="i">
+                    <default>600</default>
+                </key>
+            </schema>
+        </schemalist>
+    "#,
+    assert_not_send = true
+)]
+pub struct Settings;
+
+// Breaks the assertion
+unsafe impl Send for Settings {}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use gsettings_macro::gen_settings;
+
+#[gen_settings(xml = r#"
+        <schemalist>
+            <schema path="/io/github/seadve/test/ui/" id="io.github.seadve.test.ui">
+                <key name="is-enabled" type="b">
+                    <default>false</default>
+                </key>
+            </schema>
+        </schemalist>
+    "#, path = "/io/github/seadve/test/ui/other/")]
+pub struct Settings;
+
+fn main() {}
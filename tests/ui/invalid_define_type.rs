@@ -0,0 +1,19 @@
+use gsettings_macro::gen_settings;
+
+#[gen_settings(xml = r#"
+        <schemalist>
+            <flags id="io.github.seadve.test.ui.Style">
+                <value nick="bold" value="1"/>
+                <value nick="italic" value="2"/>
+            </flags>
+            <schema path="/io/github/seadve/test/ui/" id="io.github.seadve.test.ui">
+                <key name="style" flags="io.github.seadve.test.ui.Style">
+                    <default>[]</default>
+                </key>
+            </schema>
+        </schemalist>
+    "#)]
+#[gen_settings_define(key_name = "style", arg_type = "Style", ret_type = "Style<")]
+pub struct Settings;
+
+fn main() {}
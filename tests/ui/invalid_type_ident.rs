@@ -0,0 +1,18 @@
+use gsettings_macro::gen_settings;
+
+#[gen_settings(xml = r#"
+        <schemalist>
+            <schema path="/io/github/seadve/test/ui/" id="io.github.seadve.test.ui">
+                <key name="2x-scale" type="s">
+                    <choices>
+                        <choice value="on"/>
+                        <choice value="off"/>
+                    </choices>
+                    <default>'on'</default>
+                </key>
+            </schema>
+        </schemalist>
+    "#)]
+pub struct Settings;
+
+fn main() {}
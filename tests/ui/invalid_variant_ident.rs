@@ -0,0 +1,18 @@
+use gsettings_macro::gen_settings;
+
+#[gen_settings(xml = r#"
+        <schemalist>
+            <flags id="io.github.seadve.test.ui.Scales">
+                <value nick="1x" value="1"/>
+                <value nick="2x" value="2"/>
+            </flags>
+            <schema path="/io/github/seadve/test/ui/" id="io.github.seadve.test.ui">
+                <key name="scales" flags="io.github.seadve.test.ui.Scales">
+                    <default>['1x']</default>
+                </key>
+            </schema>
+        </schemalist>
+    "#)]
+pub struct Settings;
+
+fn main() {}
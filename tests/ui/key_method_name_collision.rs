@@ -0,0 +1,17 @@
+use gsettings_macro::gen_settings;
+
+#[gen_settings(xml = r#"
+        <schemalist>
+            <schema path="/io/github/seadve/test/ui/" id="io.github.seadve.test.ui">
+                <key name="foo" type="b">
+                    <default>false</default>
+                </key>
+                <key name="reset-foo" type="b">
+                    <default>false</default>
+                </key>
+            </schema>
+        </schemalist>
+    "#, functions_module = "settings_fns")]
+pub struct Settings;
+
+fn main() {}
@@ -0,0 +1,19 @@
+use gsettings_macro::gen_settings;
+
+#[gen_settings(xml = r#"
+        <schemalist>
+            <schema path="/io/github/seadve/test/ui/first/" id="io.github.seadve.test.ui.first">
+                <key name="width" type="i">
+                    <default>600</default>
+                </key>
+            </schema>
+            <schema path="/io/github/seadve/test/ui/second/" id="io.github.seadve.test.ui.second">
+                <key name="height" type="i">
+                    <default>400</default>
+                </key>
+            </schema>
+        </schemalist>
+    "#)]
+pub struct Settings;
+
+fn main() {}
@@ -0,0 +1,12 @@
+use gio::glib;
+use gsettings_macro::gen_settings;
+
+// Looked up in the temporary `GSETTINGS_SCHEMA_DIR` set up by the test
+#[gen_settings(id = "io.github.seadve.test.installed")]
+pub struct Settings;
+
+fn main() {
+    let _: fn(&Settings) -> Layout = Settings::layout;
+    let _: fn(&Settings, Layout) = Settings::set_layout;
+    assert_eq!(Layout::default(), Layout::List);
+}
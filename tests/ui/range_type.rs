@@ -0,0 +1,19 @@
+use gsettings_macro::gen_settings;
+
+#[gen_settings(xml = r#"
+        <schemalist>
+            <enum id="io.github.seadve.test.ui.Direction">
+                <value nick="up" value="0"/>
+                <value nick="down" value="1"/>
+            </enum>
+            <schema path="/io/github/seadve/test/ui/" id="io.github.seadve.test.ui">
+                <key name="scroll-direction" type="s">
+                    <range type="enum" id="io.github.seadve.test.ui.Direction"/>
+                    <default>'down'</default>
+                </key>
+            </schema>
+        </schemalist>
+    "#)]
+pub struct Settings;
+
+fn main() {}
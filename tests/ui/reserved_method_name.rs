@@ -0,0 +1,17 @@
+use gsettings_macro::gen_settings;
+
+#[gen_settings(xml = r#"
+        <schemalist>
+            <schema path="/io/github/seadve/test/ui/" id="io.github.seadve.test.ui">
+                <key name="hash" type="b">
+                    <default>false</default>
+                </key>
+                <key name="path" type="s">
+                    <default>''</default>
+                </key>
+            </schema>
+        </schemalist>
+    "#, settings_ext = true)]
+pub struct Settings;
+
+fn main() {}
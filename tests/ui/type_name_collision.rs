@@ -0,0 +1,25 @@
+use gsettings_macro::gen_settings;
+
+#[gen_settings(xml = r#"
+        <schemalist>
+            <schema path="/io/github/seadve/test/ui/" id="io.github.seadve.test.ui">
+                <key name="settings-builder" type="s">
+                    <choices>
+                        <choice value="default"/>
+                        <choice value="custom"/>
+                    </choices>
+                    <default>'default'</default>
+                </key>
+                <key name="settings-values" type="s">
+                    <choices>
+                        <choice value="current"/>
+                        <choice value="preset"/>
+                    </choices>
+                    <default>'current'</default>
+                </key>
+            </schema>
+        </schemalist>
+    "#, values_struct = true)]
+pub struct Settings;
+
+fn main() {}
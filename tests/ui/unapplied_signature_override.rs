@@ -0,0 +1,20 @@
+use gsettings_macro::gen_settings;
+
+#[gen_settings(xml = r#"
+        <schemalist>
+            <schema path="/io/github/seadve/test/ui/" id="io.github.seadve.test.ui">
+                <key name="color-scheme" type="s">
+                    <choices>
+                        <choice value="light"/>
+                        <choice value="dark"/>
+                    </choices>
+                    <default>"light"</default>
+                </key>
+            </schema>
+        </schemalist>
+    "#)]
+// All `s` keys have choices, so the define is not applied on any key
+#[gen_settings_define(signature = "s", arg_type = "&str", ret_type = "String")]
+pub struct Settings;
+
+fn main() {}